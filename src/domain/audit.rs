@@ -0,0 +1,126 @@
+use crate::domain::bed::BlockReason;
+use std::time::SystemTime;
+
+/// Event sent to `Hospital::subscribe` channels: the same entries as the audit log
+pub type HospitalEvent = AuditEvent;
+
+/// A state change recorded by the hospital (who went where and when)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditEvent {
+    /// Patient admitted to a bed
+    Admitted {
+        clinical_record: u32,
+        bed: u32,
+        at: SystemTime,
+    },
+    /// Patient moved from one bed to another
+    Moved {
+        clinical_record: u32,
+        from: u32,
+        to: u32,
+        at: SystemTime,
+    },
+    /// Patient discharged (bed freed)
+    Discharged {
+        clinical_record: u32,
+        bed: u32,
+        at: SystemTime,
+    },
+    /// Patient marked (or unmarked) as infectious
+    MarkedInfected {
+        clinical_record: u32,
+        bed: u32,
+        is_infected: bool,
+        at: SystemTime,
+    },
+    /// Patient VIP status changed
+    SetVip {
+        clinical_record: u32,
+        bed: u32,
+        is_vip: bool,
+        at: SystemTime,
+    },
+    /// Two patients switched beds (beds are the ones they ended up in)
+    Switched {
+        clinical_record1: u32,
+        bed1: u32,
+        clinical_record2: u32,
+        bed2: u32,
+        at: SystemTime,
+    },
+    /// Bed blocked on behalf of a VIP/infectious patient
+    Blocked {
+        bed: u32,
+        clinical_record: u32,
+        at: SystemTime,
+    },
+    /// Bed unblocked (the patient no longer requires it)
+    Unblocked {
+        bed: u32,
+        clinical_record: u32,
+        at: SystemTime,
+    },
+    /// Bed taken out of service (`on: true`) or put back into service
+    Maintenance { bed: u32, on: bool, at: SystemTime },
+    /// Bed blocked by staff (`on: true`) or one of those manual blocks lifted
+    ManualBlock {
+        bed: u32,
+        reason: BlockReason,
+        on: bool,
+        at: SystemTime,
+    },
+    /// Bed reserved for a patient (`on: true`), or the reservation cancelled or expired
+    Reserved {
+        bed: u32,
+        clinical_record: u32,
+        on: bool,
+        at: SystemTime,
+    },
+}
+
+impl AuditEvent {
+    /// Does the event concern this patient (including beds blocked on their behalf)?
+    /// Maintenance and manual blocks concern no patient.
+    pub fn involves(&self, clinical_record: u32) -> bool {
+        match *self {
+            AuditEvent::Admitted {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::Moved {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::Discharged {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::MarkedInfected {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::SetVip {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::Blocked {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::Unblocked {
+                clinical_record: crn,
+                ..
+            }
+            | AuditEvent::Reserved {
+                clinical_record: crn,
+                ..
+            } => crn == clinical_record,
+            AuditEvent::Switched {
+                clinical_record1,
+                clinical_record2,
+                ..
+            } => clinical_record1 == clinical_record || clinical_record2 == clinical_record,
+            AuditEvent::Maintenance { .. } | AuditEvent::ManualBlock { .. } => false,
+        }
+    }
+}
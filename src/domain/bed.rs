@@ -1,5 +1,58 @@
 use crate::domain::constants::{FIRST_BED_INDEX, LAST_BED_INDEX, VALID_UNITS};
+use crate::domain::error::LayoutError;
 use crate::domain::patient::Patient;
+use std::fmt;
+use std::time::SystemTime;
+
+/// Why a bed is blocked (each reason names the patient responsible)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockReason {
+    /// Shares a room with a VIP patient (CRN)
+    AdjacentVip(u32),
+    /// Shares a room with an infectious patient (CRN)
+    AdjacentInfected(u32),
+    /// Shares a room with a patient who requires isolation (CRN)
+    AdjacentIsolation(u32),
+    /// Room held private for a patient (CRN), see `Hospital::assign_private_room`
+    PrivateRoom(u32),
+    /// Blocked for a reason not tied to any patient, e.g. by staff with `Hospital::block_bed`
+    /// (no discharge releases it)
+    Other,
+}
+
+impl BlockReason {
+    /// Clinical record number of the patient responsible for the block (None for `Other`)
+    pub fn clinical_record(&self) -> Option<u32> {
+        match self {
+            BlockReason::AdjacentVip(crn)
+            | BlockReason::AdjacentInfected(crn)
+            | BlockReason::AdjacentIsolation(crn)
+            | BlockReason::PrivateRoom(crn) => Some(*crn),
+            BlockReason::Other => None,
+        }
+    }
+}
+
+impl fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockReason::AdjacentVip(crn) => write!(f, "next to VIP #{}", crn),
+            BlockReason::AdjacentInfected(crn) => write!(f, "next to infectious #{}", crn),
+            BlockReason::AdjacentIsolation(crn) => write!(f, "next to isolated #{}", crn),
+            BlockReason::PrivateRoom(crn) => write!(f, "private room of #{}", crn),
+            BlockReason::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Equipment a bed may have, which some patients require
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BedCapability {
+    /// Rated for heavy patients
+    Bariatric,
+    /// Connected to cardiac monitoring
+    Telemetry,
+}
 
 /// Possible bed states
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,37 +61,167 @@ pub enum BedState {
     Occupied(Patient),
     /// Empty bed
     Vacant,
-    /// Blocked bed (cannot be used)
+    /// Blocked bed (cannot be used) until every reason is removed
+    Blocked { reasons: Vec<BlockReason> },
+    /// Out of service (cleaning, repair...), independent of any patient
+    Maintenance,
+    /// Held for an incoming patient (CRN) until `until` (None: until cancelled), see
+    /// `Hospital::reserve_bed`
+    Reserved {
+        clinical_record: u32,
+        until: Option<SystemTime>,
+    },
+}
+
+/// What a bed state is, without its data (e.g. for rendering)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BedStateKind {
+    Occupied,
+    Vacant,
     Blocked,
+    Maintenance,
+    Reserved,
+}
+
+impl BedState {
+    /// The kind of state, without the patient or block reasons
+    pub fn kind(&self) -> BedStateKind {
+        match self {
+            BedState::Occupied(_) => BedStateKind::Occupied,
+            BedState::Vacant => BedStateKind::Vacant,
+            BedState::Blocked { .. } => BedStateKind::Blocked,
+            BedState::Maintenance => BedStateKind::Maintenance,
+            BedState::Reserved { .. } => BedStateKind::Reserved,
+        }
+    }
+
+    /// Is this a reservation whose hold is over at `now`?
+    pub fn is_expired_reservation(&self, now: SystemTime) -> bool {
+        matches!(self, BedState::Reserved { until: Some(until), .. } if *until <= now)
+    }
+
+    /// Adds block reasons to a free/blocked bed (occupied beds are left alone).
+    /// Returns true if the bed went from Vacant to Blocked.
+    pub(crate) fn add_block_reasons(&mut self, new_reasons: &[BlockReason]) -> bool {
+        match self {
+            BedState::Vacant if !new_reasons.is_empty() => {
+                *self = BedState::Blocked {
+                    reasons: new_reasons.to_vec(),
+                };
+                true
+            }
+            BedState::Blocked { reasons } => {
+                for reason in new_reasons {
+                    if !reasons.contains(reason) {
+                        reasons.push(*reason);
+                    }
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the matching block reasons; the bed becomes Vacant once none are left.
+    /// Returns true if the bed went from Blocked to Vacant.
+    pub(crate) fn remove_block_reasons(&mut self, remove: impl Fn(&BlockReason) -> bool) -> bool {
+        if let BedState::Blocked { reasons } = self {
+            reasons.retain(|r| !remove(r));
+            if reasons.is_empty() {
+                *self = BedState::Vacant;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl fmt::Display for BedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BedState::Occupied(p) => write!(f, "OCCUPIED - {}", p),
+            BedState::Vacant => write!(f, "VACANT"),
+            BedState::Blocked { reasons } => {
+                write!(f, "BLOCKED")?;
+                for (i, reason) in reasons.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { " (" } else { ", " }, reason)?;
+                }
+                if !reasons.is_empty() {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+            BedState::Maintenance => write!(f, "MAINTENANCE"),
+            BedState::Reserved {
+                clinical_record, ..
+            } => write!(f, "RESERVED for #{}", clinical_record),
+        }
+    }
+}
+
+/// Number of bed `index` in `unit`: unit * base + index, where `base` comes from
+/// `bed_number_base` (100 gives the usual UXX numbers). Checked arithmetic: an index that
+/// doesn't fit under the base or a number too large for a u32 is an error instead of
+/// silently wrapping.
+pub fn bed_number(unit: u16, index: u16, base: u32) -> Result<u32, LayoutError> {
+    let overflow = LayoutError::BedNumberOverflow { unit, index };
+    if u32::from(index) >= base {
+        return Err(overflow);
+    }
+    u32::from(unit)
+        .checked_mul(base)
+        .and_then(|n| n.checked_add(u32::from(index)))
+        .ok_or(overflow)
+}
+
+/// What unit numbers are multiplied by in bed numbers when units go up to bed `last_index`:
+/// 100 (UXX) for up to 99 beds, then the next power of ten (bed 120 of unit 1 is 1120)
+pub fn bed_number_base(last_index: u16) -> u32 {
+    let mut base = 100;
+    while u32::from(last_index) >= base {
+        base *= 10;
+    }
+    base
 }
 
 /// Hospital bed (we only store the state; the bed number is the key in the HashMap)
 #[derive(Debug, Clone)]
 pub struct Bed {
     pub state: BedState,
+    /// Equipment the bed has (none unless set in the layout, see
+    /// `Hospital::with_bed_capability`)
+    pub capabilities: Vec<BedCapability>,
 }
 
 impl Bed {
     /// Creates a new VACANT bed, validating that the number follows PDF rules.
     /// - Format: UXX → U is the unit, XX is the index (01..38).
     /// - Valid units: 1, 2, 4, 5.
-    pub fn new(number: u16) -> Self {
+    pub fn new(number: u32) -> Self {
         // Validate bed number using shared CONSTANTS
-        let unit = number / 100;
-        let bed_in_unit = number % 100;
+        Self::in_layout(number, &VALID_UNITS, LAST_BED_INDEX)
+    }
 
-        // Valid units come from VALID_UNITS constant
-        if !VALID_UNITS.contains(&unit) {
+    /// Creates a new VACANT bed, validating its number against a layout of `units` with beds
+    /// FIRST_BED_INDEX..=`last_index` each (as in `Hospital::try_with_layout`), e.g. bed 1120
+    /// when units have 120 beds
+    pub fn in_layout(number: u32, units: &[u16], last_index: u16) -> Self {
+        let base = bed_number_base(last_index);
+        let unit = number / base;
+        let bed_in_unit = number % base;
+
+        if !units.iter().any(|&u| u32::from(u) == unit) {
             panic!("Invalid unit: {}", unit);
         }
 
-        // Bed indices come from FIRST_BED_INDEX..=LAST_BED_INDEX range
-        if !(FIRST_BED_INDEX..=LAST_BED_INDEX).contains(&bed_in_unit) {
+        // Bed indices come from the FIRST_BED_INDEX..=last_index range
+        if !(u32::from(FIRST_BED_INDEX)..=u32::from(last_index)).contains(&bed_in_unit) {
             panic!("Invalid bed number: {}", bed_in_unit);
         }
 
         Bed {
             state: BedState::Vacant,
+            capabilities: Vec::new(),
         }
     }
 
@@ -49,7 +232,28 @@ impl Bed {
 
     /// Is the bed blocked?
     pub fn is_blocked(&self) -> bool {
-        matches!(self.state, BedState::Blocked)
+        matches!(self.state, BedState::Blocked { .. })
+    }
+
+    /// Is the bed out of service?
+    pub fn is_in_maintenance(&self) -> bool {
+        matches!(self.state, BedState::Maintenance)
+    }
+
+    /// Does the bed have every capability the patient requires?
+    pub fn meets_requirements(&self, patient: &Patient) -> bool {
+        patient
+            .requirements
+            .iter()
+            .all(|c| self.capabilities.contains(c))
+    }
+
+    /// Reasons the bed is blocked (empty if it isn't)
+    pub fn block_reasons(&self) -> &[BlockReason] {
+        match &self.state {
+            BedState::Blocked { reasons } => reasons,
+            _ => &[],
+        }
     }
 }
 
@@ -58,6 +262,36 @@ mod tests {
     use super::*;
     use crate::domain::patient::Gender;
 
+    #[test]
+    fn test_bed_number_is_overflow_safe() {
+        assert_eq!(bed_number(2, 5, 100), Ok(205));
+        assert_eq!(bed_number(1, 120, 1000), Ok(1120));
+        assert_eq!(bed_number(u16::MAX, 99, 100), Ok(6_553_599));
+        assert_eq!(
+            bed_number(1, 100, 100),
+            Err(LayoutError::BedNumberOverflow {
+                unit: 1,
+                index: 100
+            })
+        );
+        assert_eq!(
+            bed_number(u16::MAX, 1, 100_000),
+            Err(LayoutError::BedNumberOverflow {
+                unit: u16::MAX,
+                index: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_bed_number_base_fits_the_largest_index() {
+        assert_eq!(bed_number_base(38), 100);
+        assert_eq!(bed_number_base(99), 100);
+        assert_eq!(bed_number_base(100), 1000);
+        assert_eq!(bed_number_base(120), 1000);
+        assert_eq!(bed_number_base(u16::MAX), 100_000);
+    }
+
     #[test]
     fn test_create_valid_bed() {
         let _bed = Bed::new(101); // unit 1, index 1
@@ -75,6 +309,18 @@ mod tests {
         Bed::new(139); // Index 39 out of range 1..=38
     }
 
+    #[test]
+    fn test_bed_in_larger_units() {
+        let _bed = Bed::in_layout(1120, &[1, 2], 120); // unit 1, index 120
+        let _bed = Bed::in_layout(2001, &[1, 2], 120);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid bed number")]
+    fn test_bed_past_the_last_index_panics() {
+        Bed::in_layout(1121, &[1, 2], 120);
+    }
+
     #[test]
     fn test_bed_state_transitions() {
         let mut bed = Bed::new(102);
@@ -90,9 +336,12 @@ mod tests {
         assert!(!bed.is_blocked());
 
         // Block the bed
-        bed.state = BedState::Blocked;
+        bed.state = BedState::Blocked {
+            reasons: vec![BlockReason::AdjacentVip(12345)],
+        };
         assert!(!bed.is_available());
         assert!(bed.is_blocked());
+        assert_eq!(bed.block_reasons(), &[BlockReason::AdjacentVip(12345)]);
 
         // Make vacant again
         bed.state = BedState::Vacant;
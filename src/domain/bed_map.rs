@@ -0,0 +1,105 @@
+use crate::domain::bed::{Bed, BedState};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Index;
+
+/// What a bed without an entry looks like
+static VACANT_BED: Bed = Bed {
+    state: BedState::Vacant,
+    capabilities: Vec::new(),
+};
+
+/// Bed storage used by `Hospital`: the layout says which beds exist, and a bed with no
+/// entry is vacant. Eager hospitals store every bed; lazy ones only the beds that aren't
+/// plain vacant beds. Reads behave the same either way.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BedMap {
+    /// Every bed number of the layout, sorted
+    layout: BTreeSet<u32>,
+    /// Beds with a stored entry
+    stored: HashMap<u32, Bed>,
+}
+
+impl BedMap {
+    /// Adds a bed to the layout, storing a vacant entry for it unless `lazy`
+    pub(crate) fn add(&mut self, bed_number: u32, lazy: bool) {
+        self.layout.insert(bed_number);
+        if !lazy {
+            self.stored.insert(bed_number, VACANT_BED.clone());
+        }
+    }
+
+    /// Is the bed part of the layout?
+    pub(crate) fn contains_key(&self, bed_number: &u32) -> bool {
+        self.layout.contains(bed_number)
+    }
+
+    /// The bed, or None if it isn't part of the layout
+    pub(crate) fn get(&self, bed_number: &u32) -> Option<&Bed> {
+        if !self.contains_key(bed_number) {
+            return None;
+        }
+        Some(self.stored.get(bed_number).unwrap_or(&VACANT_BED))
+    }
+
+    /// The bed to change (stored from now on), or None if it isn't part of the layout
+    pub(crate) fn get_mut(&mut self, bed_number: &u32) -> Option<&mut Bed> {
+        if !self.contains_key(bed_number) {
+            return None;
+        }
+        Some(
+            self.stored
+                .entry(*bed_number)
+                .or_insert_with(|| VACANT_BED.clone()),
+        )
+    }
+
+    /// Number of beds in the layout
+    pub(crate) fn len(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Number of beds with a stored entry
+    #[cfg(test)]
+    pub(crate) fn stored_len(&self) -> usize {
+        self.stored.len()
+    }
+
+    /// Bed numbers of the layout, sorted
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &u32> {
+        self.layout.iter()
+    }
+
+    /// Every bed with its number, sorted by bed number
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&u32, &Bed)> {
+        self.layout.iter().map(|n| (n, &self[n]))
+    }
+
+    /// Every bed, sorted by bed number
+    pub(crate) fn values(&self) -> impl Iterator<Item = &Bed> {
+        self.iter().map(|(_, bed)| bed)
+    }
+
+    /// Stored beds to change, in no particular order (beds without an entry are vacant, so
+    /// every patient is in one of these)
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut Bed> {
+        self.stored.values_mut()
+    }
+
+    /// Drops the entries of plain vacant beds (no capabilities), which absence already
+    /// stands for
+    pub(crate) fn compact(&mut self) {
+        self.stored
+            .retain(|_, bed| bed.state != BedState::Vacant || !bed.capabilities.is_empty());
+        self.stored.shrink_to_fit();
+    }
+}
+
+impl Index<&u32> for BedMap {
+    type Output = Bed;
+
+    /// Panics if the bed isn't part of the layout
+    fn index(&self, bed_number: &u32) -> &Bed {
+        self.get(bed_number)
+            .unwrap_or_else(|| panic!("Bed {} does not exist", bed_number))
+    }
+}
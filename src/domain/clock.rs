@@ -0,0 +1,52 @@
+use std::time::SystemTime;
+#[cfg(any(test, feature = "test-utils"))]
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Source of the current time for a `Hospital` (audit timestamps, cleaning deadlines...).
+/// Injected with `Hospital::set_clock` so tests can control time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The real wall clock (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a test can keep
+/// one and hand the other to the hospital.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl ManualClock {
+    /// Starts at `start`
+    pub fn new(start: SystemTime) -> Self {
+        ManualClock {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    /// Moves the time forward
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().expect("clock lock poisoned") += by;
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().expect("clock lock poisoned")
+    }
+}
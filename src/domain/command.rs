@@ -0,0 +1,37 @@
+use crate::domain::hospital::DischargeOutcome;
+use crate::domain::patient::Patient;
+
+/// A hospital operation as data, run with `Hospital::apply` (for replay and property tests)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `Hospital::admit_patient`
+    Admit { patient: Patient, bed: u32 },
+    /// `Hospital::move_patient`
+    Move { clinical_record: u32, to: u32 },
+    /// `Hospital::switch_patients`
+    Switch {
+        clinical_record1: u32,
+        clinical_record2: u32,
+    },
+    /// `Hospital::discharge_patient`
+    Discharge { clinical_record: u32 },
+    /// `Hospital::set_patient_vip`
+    SetVip { clinical_record: u32, is_vip: bool },
+    /// `Hospital::mark_patient_as_infected`
+    MarkInfected { clinical_record: u32 },
+    /// `Hospital::unmark_patient_as_infected`
+    UnmarkInfected { clinical_record: u32 },
+    /// `Hospital::set_maintenance`
+    SetMaintenance { bed: u32, on: bool },
+}
+
+/// What a successful `Command` returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// The operation returns nothing
+    Done,
+    /// Result of a discharge
+    Discharged(DischargeOutcome),
+    /// New bed of a relocated roommate, if any (`SetVip`, `MarkInfected`)
+    Relocated(Option<u32>),
+}
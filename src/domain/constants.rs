@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Unidades válidas del hospital (PDF: 1, 2, 4 y 5).
 pub const VALID_UNITS: [u16; 4] = [1, 2, 4, 5];
 
@@ -5,5 +7,23 @@ pub const VALID_UNITS: [u16; 4] = [1, 2, 4, 5];
 pub const FIRST_BED_INDEX: u16 = 1;
 pub const LAST_BED_INDEX: u16 = 38;
 
+/// Camas por habitación (PDF: habitaciones dobles, pares impar/par consecutivos).
+pub const BEDS_PER_ROOM: u16 = 2;
+
+/// Unidad pediátrica (PDF: los menores de 13 años solo en la unidad 5).
+pub const PEDIATRIC_UNIT: u16 = 5;
+
+/// Edad (exclusiva) por debajo de la cual el paciente va a la unidad pediátrica.
+pub const PEDIATRIC_MAX_AGE: u8 = 13;
+
+/// Edad (exclusiva) por debajo de la cual solo se comparte habitación con otros menores.
+pub const MINOR_SHARED_ROOM_MAX_AGE: u8 = 16;
+
+/// Edad máxima aceptada al dar de alta un paciente con `Patient::try_new` (errores de tecleo).
+pub const MAX_PATIENT_AGE: u8 = 130;
+
+/// Estancia media por defecto con la que `Hospital::next_likely_free_bed` estima las altas (5 días).
+pub const DEFAULT_AVERAGE_LENGTH_OF_STAY: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
 /// Útil para tests o métricas (4 unidades * 38 camas = 152).
 pub const TOTAL_BEDS: usize = VALID_UNITS.len() * (LAST_BED_INDEX as usize);
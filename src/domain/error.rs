@@ -0,0 +1,200 @@
+use crate::domain::bed::{BedCapability, BlockReason};
+use crate::domain::constants::PEDIATRIC_UNIT;
+use std::fmt;
+
+/// Errors returned by hospital operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HospitalError {
+    /// The bed number is not part of the hospital
+    BedNotFound(u32),
+    /// The bed is occupied or blocked
+    BedNotAvailable(u32),
+    /// The bed has no patient in it (vacant or blocked)
+    BedNotOccupied(u32),
+    /// The bed has a patient in it
+    BedOccupied(u32),
+    /// No admitted patient has this clinical record number
+    PatientNotFound(u32),
+    /// The patient is already admitted, in this bed
+    PatientAlreadyAdmitted(u32),
+    /// The patient already has this bed reserved
+    PatientAlreadyReserved(u32),
+    /// The clinical record number doesn't have 5 digits
+    InvalidClinicalRecord(u32),
+    /// The bed lacks equipment the patient requires
+    MissingBedCapability { bed: u32, capability: BedCapability },
+    /// Blocks tied to a patient (VIP, infection...) follow that patient and can't be added
+    /// or lifted by hand; only `BlockReason::Other` can
+    NotAManualBlock(BlockReason),
+    /// The unit already holds as many patients of this gender as its cap allows
+    GenderCapReached(u16),
+    /// Pediatric patients (under 13 by default) must be in the pediatric unit
+    /// (`PEDIATRIC_UNIT`)
+    PediatricUnitRequired,
+    /// The unit only admits patients of another gender
+    UnitGenderMismatch(u16),
+    /// Roommates must have the same gender
+    GenderMismatch,
+    /// Minors (under 16 by default) can only share with other minors
+    AgeGroupMismatch,
+    /// Nobody can share a room with an infectious or VIP patient
+    RoommateRequiresIsolation,
+    /// An infectious/VIP patient needs the adjacent bed free to block it
+    AdjacentBedNotFree,
+    /// The patient already in `bed` (`clinical_record`) can't share a room with the incoming
+    /// one; `reason` is the rule that failed (gender, age group, isolation...)
+    RoomIncompatible {
+        bed: u32,
+        clinical_record: u32,
+        reason: Box<HospitalError>,
+    },
+    /// New admission rules would break a rule for the patient already in `bed`
+    /// (`clinical_record`); `reason` is the rule they would break
+    RulesViolatedBy {
+        bed: u32,
+        clinical_record: u32,
+        reason: Box<HospitalError>,
+    },
+    /// There is no free bed to relocate a roommate to
+    NoBedForRoommate,
+    /// There is no free bed the patient could go to
+    NoCompatibleBed,
+}
+
+impl fmt::Display for HospitalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HospitalError::BedNotFound(bed) => write!(f, "Bed {} does not exist", bed),
+            HospitalError::BedNotAvailable(bed) => write!(f, "Bed {} is not available", bed),
+            HospitalError::BedNotOccupied(bed) => write!(f, "Bed {} is not occupied", bed),
+            HospitalError::BedOccupied(bed) => write!(f, "Bed {} is occupied", bed),
+            HospitalError::PatientNotFound(crn) => write!(f, "Patient {} not found", crn),
+            HospitalError::PatientAlreadyAdmitted(bed) => {
+                write!(f, "Patient is already admitted in bed {}", bed)
+            }
+            HospitalError::PatientAlreadyReserved(bed) => {
+                write!(f, "Patient already has bed {} reserved", bed)
+            }
+            HospitalError::InvalidClinicalRecord(crn) => {
+                write!(f, "Clinical record number {} must have 5 digits", crn)
+            }
+            HospitalError::MissingBedCapability { bed, capability } => {
+                write!(f, "Bed {} has no {:?} capability", bed, capability)
+            }
+            HospitalError::NotAManualBlock(reason) => {
+                write!(f, "A bed can't be blocked by hand as {}", reason)
+            }
+            HospitalError::GenderCapReached(unit) => {
+                write!(f, "Unit {} has reached its cap for this gender", unit)
+            }
+            HospitalError::PediatricUnitRequired => {
+                write!(f, "Pediatric patients must be in unit {}", PEDIATRIC_UNIT)
+            }
+            HospitalError::UnitGenderMismatch(unit) => {
+                write!(f, "Unit {} is reserved for another gender", unit)
+            }
+            HospitalError::GenderMismatch => write!(f, "Roommates must have the same gender"),
+            HospitalError::AgeGroupMismatch => {
+                write!(f, "Minors can only share a room with other minors")
+            }
+            HospitalError::RoommateRequiresIsolation => {
+                write!(f, "Cannot share a room with an infectious or VIP patient")
+            }
+            HospitalError::AdjacentBedNotFree => write!(
+                f,
+                "Patient requires the adjacent bed to be blocked, but it is not free"
+            ),
+            HospitalError::RoomIncompatible {
+                bed,
+                clinical_record,
+                reason,
+            } => write!(
+                f,
+                "Incompatible with patient {} in bed {}: {}",
+                clinical_record, bed, reason
+            ),
+            HospitalError::RulesViolatedBy {
+                bed,
+                clinical_record,
+                reason,
+            } => write!(
+                f,
+                "Patient {} in bed {} would break the new rules: {}",
+                clinical_record, bed, reason
+            ),
+            HospitalError::NoBedForRoommate => write!(f, "No available bed to relocate roommate"),
+            HospitalError::NoCompatibleBed => write!(f, "No available bed for the patient"),
+        }
+    }
+}
+
+impl std::error::Error for HospitalError {}
+
+/// Errors returned when creating a patient with `Patient::try_new`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatientError {
+    /// The clinical record number doesn't have 5 digits
+    InvalidClinicalRecord(u32),
+    /// The age is above the realistic maximum (`MAX_PATIENT_AGE`)
+    InvalidAge(u8),
+}
+
+impl fmt::Display for PatientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatientError::InvalidClinicalRecord(crn) => {
+                write!(f, "Clinical record number {} must have 5 digits", crn)
+            }
+            PatientError::InvalidAge(age) => write!(f, "Invalid patient age: {}", age),
+        }
+    }
+}
+
+impl std::error::Error for PatientError {}
+
+/// Errors found when validating a bed layout (units, bed indices and room size)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The first bed index is greater than the last one
+    EmptyBedRange { first: u16, last: u16 },
+    /// A unit appears more than once
+    DuplicateUnit(u16),
+    /// A unit/index pair doesn't fit in a u32 bed number
+    BedNumberOverflow { unit: u16, index: u16 },
+    /// Rooms must have at least one bed and no more than a unit holds
+    InvalidRoomSize(u16),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::EmptyBedRange { first, last } => {
+                write!(f, "Bed index range {}..={} is empty", first, last)
+            }
+            LayoutError::DuplicateUnit(unit) => write!(f, "Unit {} is listed twice", unit),
+            LayoutError::BedNumberOverflow { unit, index } => {
+                write!(f, "Bed {} of unit {} overflows the bed number", index, unit)
+            }
+            LayoutError::InvalidRoomSize(size) => write!(f, "Invalid room size: {} beds", size),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Error found while loading a manifest with `Hospital::from_manifest`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    /// 1-based line number
+    pub line: usize,
+    /// What was wrong with it
+    pub reason: String,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Manifest line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ManifestError {}
@@ -1,510 +1,2911 @@
-use crate::domain::bed::{Bed, BedState};
-use crate::domain::constants::{FIRST_BED_INDEX, LAST_BED_INDEX, VALID_UNITS};
-use crate::domain::patient::Patient;
-use std::collections::HashMap;
+use crate::domain::audit::{AuditEvent, HospitalEvent};
+use crate::domain::bed::{
+    bed_number, bed_number_base, Bed, BedCapability, BedState, BedStateKind, BlockReason,
+};
+use crate::domain::bed_map::BedMap;
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::command::{Command, CommandOutcome};
+use crate::domain::constants::{
+    BEDS_PER_ROOM, DEFAULT_AVERAGE_LENGTH_OF_STAY, FIRST_BED_INDEX, LAST_BED_INDEX, PEDIATRIC_UNIT,
+    VALID_UNITS,
+};
+use crate::domain::error::{HospitalError, LayoutError, ManifestError};
+use crate::domain::invariant::InvariantViolation;
+use crate::domain::manifest::{manifest_line, parse_manifest, ManifestEntry};
+use crate::domain::observer::HospitalObserver;
+use crate::domain::patient::{is_valid_clinical_record, Gender, Patient};
+use crate::domain::relocation::{RelocationFailurePolicy, RelocationStrategy, SameUnitStrategy};
+use crate::domain::room::Room;
+use crate::domain::rules::{compatible_roommates, compatible_roommates_any_gender, AdmissionRules};
+use crate::domain::snapshot::HospitalSnapshot;
+use crate::domain::summary::{OccupancySummary, UnitOccupancy};
+use crate::domain::warning::AdmissionWarning;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, SystemTime};
+
+/// Beds changed by `Hospital::discharge_patient`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DischargeOutcome {
+    /// The bed the patient left (now vacant)
+    pub freed_bed: u32,
+    /// The roommate bed that was blocked on the patient's behalf and is now vacant
+    /// (the first one, in larger rooms)
+    pub unblocked_bed: Option<u32>,
+}
+
+/// An infectious patient as listed by `Hospital::infection_report`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfectionEntry {
+    pub clinical_record: u32,
+    pub name: String,
+    pub bed: u32,
+    /// The roommate bed blocked because of the infection (the first one, in larger rooms)
+    pub blocked_bed: Option<u32>,
+}
+
+/// An occupied room as listed by `Hospital::patients_by_room`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoomOccupancy {
+    /// Every bed of the room, sorted, with its patient (None if vacant, blocked or in
+    /// maintenance)
+    pub beds: Vec<(u32, Option<Patient>)>,
+}
 
 /// Main structure that manages all hospital beds
 pub struct Hospital {
-    /// Bed map, indexed by bed number UXX (u16)
-    pub beds: HashMap<u16, Bed>,
+    /// Bed map, indexed by bed number (UXX in the default layout); beds without an entry are
+    /// vacant. Private so every change goes through the operations below and their rules;
+    /// read it with `bed` / `iter_beds`.
+    beds: BedMap,
+    /// Bed numbers of each unit, sorted (the layout never changes after construction)
+    unit_index: BTreeMap<u16, Vec<u32>>,
+    /// What unit numbers are multiplied by in bed numbers (100 unless units are larger,
+    /// see `bed_number_base`)
+    bed_base: u32,
+    /// Number of consecutive beds that make up a room (2 = even/odd pairs)
+    beds_per_room: u16,
+    /// Single-occupancy beds: rooms of their own, with no roommate
+    single_rooms: HashSet<u32>,
+    /// Beds of family rooms, where roommates of different gender may share
+    family_rooms: HashSet<u32>,
+    /// Units where every patient is treated as infectious (outbreak isolation)
+    isolation_units: HashSet<u16>,
+    /// Units that only admit one gender (units not listed are mixed)
+    unit_genders: HashMap<u16, Gender>,
+    /// Most patients of a gender a unit may hold (no cap if not listed)
+    gender_caps: HashMap<(u16, Gender), usize>,
+    /// Patients (CRN) holding their whole room, wherever they are moved
+    private_rooms: HashSet<u32>,
+    /// When each admitted patient (CRN) was admitted; kept by identity, so moves and
+    /// switches don't change it
+    admission_times: HashMap<u32, SystemTime>,
+    /// Beds being cleaned by `cycle_bed`, and when they can go back into service
+    cleaning_until: HashMap<u32, SystemTime>,
+    /// Expected stay of a patient, used to estimate when beds free up
+    average_length_of_stay: Duration,
+    /// Where the current time comes from
+    clock: Box<dyn Clock>,
+    /// Age thresholds applied on admission
+    rules: AdmissionRules,
+    /// Every state change, in the order it happened
+    audit_log: Vec<AuditEvent>,
+    /// Patients waiting for a bed, first come first served
+    waitlist: VecDeque<Patient>,
+    /// Notified of every change after it has been committed
+    observers: Vec<Box<dyn HospitalObserver>>,
+    /// Channels of `subscribe`; dropped once their receiver is gone
+    subscribers: Vec<Sender<HospitalEvent>>,
+    /// Events of an all-or-nothing operation in progress (see `atomically`), published only
+    /// once it succeeds
+    held_events: Option<Vec<AuditEvent>>,
+    /// Where roommates go when they have to leave a room
+    relocation: Box<dyn RelocationStrategy>,
+    /// What to do with a roommate who can't be placed anywhere
+    on_relocation_failure: RelocationFailurePolicy,
 }
 
 impl Hospital {
     /// Creates a hospital with ALL valid beds according to shared constants
     pub fn new() -> Self {
-        let mut beds = HashMap::new();
+        Self::try_new().unwrap()
+    }
+
+    /// Like `new`, but checks first that the layout constants make sense
+    pub fn try_new() -> Result<Self, LayoutError> {
+        Self::try_with_beds_per_room(BEDS_PER_ROOM)
+    }
+
+    /// Creates a hospital whose rooms have `beds_per_room` consecutive beds (e.g. 4-bed bays).
+    /// The last room of a unit may be smaller if the unit size isn't a multiple.
+    pub fn with_beds_per_room(beds_per_room: u16) -> Self {
+        Self::try_with_beds_per_room(beds_per_room).unwrap()
+    }
+
+    /// Like `with_beds_per_room`, but returns an error instead of panicking on a bad layout
+    pub fn try_with_beds_per_room(beds_per_room: u16) -> Result<Self, LayoutError> {
+        Self::build(&VALID_UNITS, LAST_BED_INDEX, beds_per_room, false)
+    }
+
+    /// Creates a hospital with `beds_per_unit` beds in each of `units`, in rooms of
+    /// `beds_per_room`. Units of 100 beds or more get longer bed numbers: bed 120 of unit 1
+    /// is 1120, and bed 1 is 1001.
+    pub fn try_with_layout(
+        units: &[u16],
+        beds_per_unit: u16,
+        beds_per_room: u16,
+    ) -> Result<Self, LayoutError> {
+        Self::build(units, beds_per_unit, beds_per_room, false)
+    }
+
+    /// Like `new`, but in lazy mode: only beds that aren't plain vacant beds are stored,
+    /// which saves memory on large, mostly empty layouts. Every query answers the same.
+    pub fn new_lazy() -> Self {
+        Self::build(&VALID_UNITS, LAST_BED_INDEX, BEDS_PER_ROOM, true).unwrap()
+    }
+
+    /// Builds the layout (beds FIRST_BED_INDEX..=`last_index` of each unit), storing every
+    /// bed unless `lazy`
+    fn build(
+        units: &[u16],
+        last_index: u16,
+        beds_per_room: u16,
+        lazy: bool,
+    ) -> Result<Self, LayoutError> {
+        validate_layout(units, FIRST_BED_INDEX, last_index, beds_per_room)?;
+
+        let bed_base = bed_number_base(last_index);
+        let mut beds = BedMap::default();
+        let mut unit_index = BTreeMap::new();
+
+        // Create beds for each unit and for each index FIRST..=last
+        for &unit in units {
+            for idx in FIRST_BED_INDEX..=last_index {
+                let bed_number = bed_number(unit, idx, bed_base)?; // UXX format by default
+                beds.add(bed_number, lazy);
+                unit_index
+                    .entry(unit)
+                    .or_insert_with(Vec::new)
+                    .push(bed_number);
+            }
+        }
+
+        Ok(Hospital {
+            beds,
+            unit_index,
+            bed_base,
+            beds_per_room,
+            single_rooms: HashSet::new(),
+            family_rooms: HashSet::new(),
+            isolation_units: HashSet::new(),
+            unit_genders: HashMap::new(),
+            gender_caps: HashMap::new(),
+            private_rooms: HashSet::new(),
+            admission_times: HashMap::new(),
+            cleaning_until: HashMap::new(),
+            average_length_of_stay: DEFAULT_AVERAGE_LENGTH_OF_STAY,
+            clock: Box::new(SystemClock),
+            rules: AdmissionRules::default(),
+            audit_log: Vec::new(),
+            waitlist: VecDeque::new(),
+            observers: Vec::new(),
+            subscribers: Vec::new(),
+            held_events: None,
+            relocation: Box::new(SameUnitStrategy),
+            on_relocation_failure: RelocationFailurePolicy::Abort,
+        })
+    }
+
+    /// Turns the given beds into single rooms (isolation or private rooms) with no roommate,
+    /// so the gender/age sharing rules never apply to them. Panics if a bed doesn't exist.
+    pub fn with_single_rooms(mut self, bed_numbers: &[u32]) -> Self {
+        for &bed_number in bed_numbers {
+            if !self.beds.contains_key(&bed_number) {
+                panic!("Bed {} does not exist", bed_number);
+            }
+            self.single_rooms.insert(bed_number);
+        }
+        self
+    }
+
+    /// Gives the given beds a piece of equipment (e.g. telemetry), so patients who require it
+    /// can be placed there. Panics if a bed doesn't exist.
+    pub fn with_bed_capability(mut self, capability: BedCapability, bed_numbers: &[u32]) -> Self {
+        for &bed_number in bed_numbers {
+            let bed = self
+                .beds
+                .get_mut(&bed_number)
+                .unwrap_or_else(|| panic!("Bed {} does not exist", bed_number));
+            if !bed.capabilities.contains(&capability) {
+                bed.capabilities.push(capability);
+            }
+        }
+        self
+    }
+
+    /// Designates the rooms of the given beds as family rooms: the gender rule doesn't apply
+    /// there (age group, infection and VIP rules still do). Panics if a bed doesn't exist.
+    pub fn with_family_rooms(mut self, bed_numbers: &[u32]) -> Self {
+        for &bed_number in bed_numbers {
+            if !self.beds.contains_key(&bed_number) {
+                panic!("Bed {} does not exist", bed_number);
+            }
+            let members = self.room_members(bed_number);
+            self.family_rooms.extend(members);
+        }
+        self
+    }
+
+    /// Is the bed in a family room (mixed-gender sharing allowed)?
+    pub fn is_family_room(&self, bed_number: u32) -> bool {
+        self.family_rooms.contains(&bed_number)
+    }
+
+    /// Replaces how displaced roommates are placed (same unit first by default)
+    pub fn set_relocation_strategy(&mut self, strategy: Box<dyn RelocationStrategy>) {
+        self.relocation = strategy;
+    }
+
+    /// Sets what happens when a displaced roommate can't be placed (abort by default)
+    pub fn set_relocation_failure_policy(&mut self, policy: RelocationFailurePolicy) {
+        self.on_relocation_failure = policy;
+    }
+
+    /// Returns a bed by number (None if it doesn't exist)
+    pub fn bed(&self, bed_number: u32) -> Option<&Bed> {
+        self.beds.get(&bed_number)
+    }
+
+    /// State of a bed (None if it doesn't exist)
+    pub fn bed_state(&self, bed_number: u32) -> Option<&BedState> {
+        self.bed(bed_number).map(|bed| &bed.state)
+    }
+
+    /// Drops the stored entries of vacant beds, which pile up in lazy mode as patients
+    /// leave. Only memory changes: every query answers the same.
+    pub fn compact(&mut self) {
+        self.beds.compact();
+    }
+
+    /// Number of beds in the hospital
+    pub fn bed_count(&self) -> usize {
+        self.beds.len()
+    }
+
+    /// Raw number of physical beds, whatever their state (same as `bed_count`)
+    pub fn total_beds(&self) -> usize {
+        self.bed_count()
+    }
+
+    /// Units that have beds in this hospital, sorted
+    pub fn units(&self) -> Vec<u16> {
+        self.unit_index.keys().copied().collect()
+    }
+
+    /// Unit of a bed number under this hospital's numbering (2 for bed 205)
+    pub fn unit_of(&self, bed_number: u32) -> u16 {
+        (bed_number / self.bed_base) as u16
+    }
+
+    /// Index of a bed within its unit (5 for bed 205)
+    fn index_of(&self, bed_number: u32) -> u16 {
+        (bed_number % self.bed_base) as u16
+    }
+
+    /// Bed numbers of a unit, sorted (empty if the unit doesn't exist)
+    pub fn beds_in_unit(&self, unit: u16) -> Vec<u32> {
+        self.unit_index.get(&unit).cloned().unwrap_or_default()
+    }
+
+    /// Iterates over every bed as (bed number, bed), sorted by bed number
+    pub fn iter_beds(&self) -> impl Iterator<Item = (u32, &Bed)> {
+        self.beds.iter().map(|(&n, bed)| (n, bed))
+    }
+
+    /// Every bed number in display order: by unit, then by index within the unit.
+    /// Explicit so the order doesn't depend on how bed numbers are encoded.
+    pub fn sorted_bed_numbers(&self) -> Vec<u32> {
+        let mut numbers: Vec<u32> = self.beds.keys().copied().collect();
+        numbers.sort_unstable_by_key(|&n| (self.unit_of(n), self.index_of(n)));
+        numbers
+    }
 
-        // Create beds for each unit (VALID_UNITS) and for each index FIRST..=LAST
-        for &unit in &VALID_UNITS {
-            for idx in FIRST_BED_INDEX..=LAST_BED_INDEX {
-                let bed_number = unit * 100 + idx; // UXX format
-                beds.insert(bed_number, Bed::new(bed_number));
+    /// Builds a hospital from a text manifest (handy for demos and tests), one bed per line:
+    /// `201: 10001 "Maria Garcia" 30 F vip` (flags: vip, infected, isolation), `205: BLOCKED`,
+    /// `206: MAINTENANCE`, `207: VACANT` or `208: RESERVED 10002 until 1700000000`.
+    /// Patients go through the normal admission rules, in order. Blank lines and `#`
+    /// comments are ignored; reservations that have already expired are released.
+    pub fn from_manifest(text: &str) -> Result<Self, ManifestError> {
+        Self::from_manifest_with_clock(text, Box::new(SystemClock))
+    }
+
+    /// `from_manifest` with the clock the hospital will use (and judge expiries by)
+    fn from_manifest_with_clock(text: &str, clock: Box<dyn Clock>) -> Result<Self, ManifestError> {
+        let mut hospital = Hospital::new();
+        hospital.set_clock(clock);
+
+        for (line, bed_number, entry) in parse_manifest(text)? {
+            let error = |reason: String| ManifestError { line, reason };
+            match entry {
+                ManifestEntry::Occupied(patient) => hospital
+                    .admit_patient(&patient, bed_number)
+                    .map_err(|e| error(e.to_string()))?,
+                ManifestEntry::Vacant => {}
+                ManifestEntry::Blocked => hospital
+                    .block_bed(bed_number, BlockReason::Other)
+                    .map_err(|e| error(e.to_string()))?,
+                ManifestEntry::Maintenance => hospital
+                    .set_maintenance(bed_number, true)
+                    .map_err(|e| error(e.to_string()))?,
+                ManifestEntry::Reserved {
+                    clinical_record,
+                    until,
+                } => hospital
+                    .reserve_bed_until(bed_number, clinical_record, until)
+                    .map_err(|e| error(e.to_string()))?,
+            }
+        }
+
+        hospital.expire_reservations();
+        Ok(hospital)
+    }
+
+    /// The hospital as a manifest `from_manifest` can read back: patients first (so their
+    /// blocks are in place), then manual blocks, maintenance and reservations, each sorted by
+    /// bed number. Only what the manifest format holds is kept (no tags, private rooms or
+    /// unit settings), and reloading replays the admission rules.
+    pub fn to_manifest(&self) -> String {
+        let (patients, others): (Vec<_>, Vec<_>) = self
+            .iter_beds()
+            .filter_map(|(bed_number, bed)| Some((bed_number, bed, manifest_line(bed)?)))
+            .partition(|(_, bed, _)| matches!(bed.state, BedState::Occupied(_)));
+
+        patients
+            .into_iter()
+            .chain(others)
+            .map(|(bed_number, _, line)| format!("{}: {}\n", bed_number, line))
+            .collect()
+    }
+
+    /// Writes `to_manifest` to a file
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_manifest())
+    }
+
+    /// Reads a hospital saved with `save_to_file`, judging reservations by `clock`: the ones
+    /// that expired while it was saved are released. A malformed manifest is an
+    /// `InvalidData` error.
+    pub fn load_from_file(path: impl AsRef<Path>, clock: Box<dyn Clock>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_manifest_with_clock(&text, clock)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Admission rules currently in force
+    pub fn admission_rules(&self) -> &AdmissionRules {
+        &self.rules
+    }
+
+    /// Replaces the admission rules (e.g. a pediatric threshold of 18 instead of 13).
+    /// Rejected with `RulesViolatedBy` if a patient already admitted would break them (a
+    /// 16-year-old outside the pediatric unit when the threshold goes up to 18, a VIP
+    /// sharing their room when VIPs start blocking it...). Beds blocked only because of the
+    /// old rules are freed.
+    pub fn set_admission_rules(&mut self, rules: AdmissionRules) -> Result<(), HospitalError> {
+        let before = self.validate_invariants();
+        let previous = std::mem::replace(&mut self.rules, rules);
+        let conflict = self
+            .validate_invariants()
+            .into_iter()
+            .filter(|violation| !before.contains(violation))
+            .find_map(|violation| self.rules_conflict(&violation));
+        if let Some(conflict) = conflict {
+            self.rules = previous;
+            return Err(conflict);
+        }
+
+        // e.g. the neighbours of VIPs once VIPs no longer block their room
+        self.reclaim_orphaned_blocks();
+        Ok(())
+    }
+
+    /// The error a rule-dependent invariant violation stands for, naming the patient who
+    /// breaks the rule (None for violations the rules don't cause)
+    fn rules_conflict(&self, violation: &InvariantViolation) -> Option<HospitalError> {
+        let (bed, reason) = match *violation {
+            InvariantViolation::ChildOutsidePediatricUnit { bed, .. } => {
+                (bed, HospitalError::PediatricUnitRequired)
+            }
+            InvariantViolation::AgeGroupMismatch { bed, .. } => {
+                (bed, HospitalError::AgeGroupMismatch)
+            }
+            InvariantViolation::SharedIsolationRoom { bed, .. } => {
+                (bed, HospitalError::RoommateRequiresIsolation)
             }
+            _ => return None,
+        };
+        let BedState::Occupied(patient) = &self.beds[&bed].state else {
+            return None;
+        };
+        let clinical_record = patient.clinical_record_number;
+        Some(HospitalError::RulesViolatedBy {
+            bed,
+            clinical_record,
+            reason: Box::new(reason),
+        })
+    }
+
+    /// Puts a whole unit in isolation mode (or takes it out). New admissions there block the
+    /// rest of their room as if the patient were infectious; patients already in it are left
+    /// where they are.
+    pub fn set_unit_isolation(&mut self, unit: u16, on: bool) {
+        if on {
+            self.isolation_units.insert(unit);
+        } else if self.isolation_units.remove(&unit) {
+            // Rooms blocked only because of the isolation are usable again
+            self.reclaim_orphaned_blocks();
         }
+    }
+
+    /// Reserves a whole unit for one gender (`None` makes it mixed again). Admissions of
+    /// other genders are rejected even into empty rooms; patients already in it are left
+    /// where they are.
+    pub fn set_unit_gender(&mut self, unit: u16, gender: Option<Gender>) {
+        match gender {
+            Some(gender) => self.unit_genders.insert(unit, gender),
+            None => self.unit_genders.remove(&unit),
+        };
+    }
+
+    /// The only gender a unit admits (None if it's mixed)
+    pub fn unit_gender(&self, unit: u16) -> Option<Gender> {
+        self.unit_genders.get(&unit).copied()
+    }
+
+    /// Caps how many patients of `gender` unit `unit` may hold (for ward balance). Admissions
+    /// over the cap are rejected with `GenderCapReached`; patients already there stay.
+    pub fn set_gender_cap(&mut self, unit: u16, gender: Gender, max: usize) {
+        self.gender_caps.insert((unit, gender), max);
+    }
+
+    /// How many more patients of `gender` unit `unit` may take under its cap, not counting
+    /// the patients in `except` (who may already be there, or be about to leave). None if
+    /// there is no cap.
+    fn gender_cap_left(&self, unit: u16, gender: Gender, except: &[u32]) -> Option<usize> {
+        let max = *self.gender_caps.get(&(unit, gender))?;
+        let present = self
+            .beds_in_unit(unit)
+            .iter()
+            .filter(|n| match &self.beds[n].state {
+                BedState::Occupied(p) => {
+                    p.gender == gender && !except.contains(&p.clinical_record_number)
+                }
+                _ => false,
+            })
+            .count();
+        Some(max.saturating_sub(present))
+    }
 
-        Hospital { beds }
+    /// Whether a unit is in isolation mode
+    pub fn is_unit_isolated(&self, unit: u16) -> bool {
+        self.isolation_units.contains(&unit)
     }
 
-    /// Returns the roommate bed number (same room, consecutive number)
+    /// Returns the roommate bed number (same room, consecutive number) for two-bed rooms.
+    /// Use `room_members` when the hospital has a different room size.
     #[inline]
-    pub fn roommate_of(bed_number: u16) -> u16 {
-        if bed_number % 2 == 0 {
+    pub fn roommate_of(bed_number: u32) -> u32 {
+        if bed_number.is_multiple_of(2) {
             bed_number - 1
         } else {
             bed_number + 1
         }
     }
 
+    /// Returns the roommate bed of `bed_number` in this hospital's layout (the first other bed
+    /// of the room). None for single rooms and for beds that don't exist.
+    pub fn roommate_of_bed(&self, bed_number: u32) -> Option<u32> {
+        self.other_room_members(bed_number).first().copied()
+    }
+
+    /// Returns every bed in the same room as `bed_number` (including itself), sorted.
+    /// Empty if the bed doesn't exist.
+    pub fn room_members(&self, bed_number: u32) -> Vec<u32> {
+        if !self.beds.contains_key(&bed_number) {
+            return Vec::new();
+        }
+        if self.single_rooms.contains(&bed_number) {
+            return vec![bed_number];
+        }
+
+        let idx = self.index_of(bed_number);
+        let first =
+            FIRST_BED_INDEX + (idx - FIRST_BED_INDEX) / self.beds_per_room * self.beds_per_room;
+        let unit_start = bed_number - u32::from(idx);
+
+        (first..first + self.beds_per_room)
+            .map(|i| unit_start + u32::from(i))
+            .filter(|n| self.beds.contains_key(n) && !self.single_rooms.contains(n))
+            .collect()
+    }
+
+    /// Are the two beds in the same room under this hospital's layout? (a bed is in its own
+    /// room; false if either bed doesn't exist)
+    pub fn same_room(&self, a: u32, b: u32) -> bool {
+        self.room_members(a).contains(&b)
+    }
+
+    /// Returns the room `bed_number` belongs to. None if the bed doesn't exist.
+    pub fn room(&self, bed_number: u32) -> Option<Room<'_>> {
+        let members = self.room_members(bed_number);
+        if members.is_empty() {
+            return None;
+        }
+        Some(Room::new(
+            members.into_iter().map(|n| (n, &self.beds[&n])).collect(),
+        ))
+    }
+
+    /// Returns every room of `unit`, sorted by first bed
+    pub fn rooms_in_unit(&self, unit: u16) -> Vec<Room<'_>> {
+        self.beds_in_unit(unit)
+            .into_iter()
+            .filter_map(|n| self.room(n).filter(|room| room.first_bed() == n))
+            .collect()
+    }
+
+    /// Returns the occupied beds of the room `bed_number` belongs to (including itself), sorted.
+    /// Empty if the bed doesn't exist.
+    pub fn room_occupants(&self, bed_number: u32) -> Vec<(u32, &Patient)> {
+        self.room(bed_number)
+            .map(|room| room.occupants())
+            .unwrap_or_default()
+    }
+
+    /// Returns the patient sharing a room with `clinical_record` (the first one, in larger
+    /// rooms). None if the patient isn't admitted or nobody else is in the room.
+    pub fn roommate_patient(&self, clinical_record: u32) -> Option<&Patient> {
+        let (bed_number, _) = self.find_patient_info(clinical_record)?;
+        self.room_occupants(bed_number)
+            .into_iter()
+            .find(|&(n, _)| n != bed_number)
+            .map(|(_, p)| p)
+    }
+
+    /// Returns the roommate bed of `bed_number` if it is vacant (the first vacant one, in
+    /// larger rooms). None if it's occupied, blocked or in maintenance, or there is none.
+    pub fn free_bed_in_room(&self, bed_number: u32) -> Option<u32> {
+        self.other_room_members(bed_number)
+            .into_iter()
+            .find(|n| self.beds[n].state == BedState::Vacant)
+    }
+
+    /// Room members other than the bed itself
+    fn other_room_members(&self, bed_number: u32) -> Vec<u32> {
+        self.room_members(bed_number)
+            .into_iter()
+            .filter(|&n| n != bed_number)
+            .collect()
+    }
+
     // ----------------- Operations -----------------
 
-    /// Admits a new patient to an empty bed (applies ALL rules from the PDF)
-    pub fn admit_patient(&mut self, patient: Patient, bed_number: u16) -> Result<(), String> {
-        // 1) Check bed exists
-        let bed = self.beds.get(&bed_number).ok_or("Bed does not exist")?;
+    /// Admits a new patient to an empty bed (applies ALL rules from the PDF).
+    /// The patient is only cloned into the bed on success, so callers keep it on failure.
+    /// A patient who already holds a bed gets `PatientAlreadyAdmitted`.
+    pub fn admit_patient(
+        &mut self,
+        patient: &Patient,
+        bed_number: u32,
+    ) -> Result<(), HospitalError> {
+        let clinical_record = patient.clinical_record_number;
+        // The same patient can't hold two beds
+        if let Some((existing_bed, _)) = self.find_patient_info(clinical_record) {
+            return Err(HospitalError::PatientAlreadyAdmitted(existing_bed));
+        }
+        let blocked = self.place_patient(patient, bed_number)?;
 
-        // 2) Check bed is available
-        if !bed.is_available() {
-            return Err("Bed is not available".to_string());
+        let at = self.now();
+        self.admission_times.insert(clinical_record, at);
+        self.record(AuditEvent::Admitted {
+            clinical_record,
+            bed: bed_number,
+            at,
+        });
+        for bed in blocked {
+            self.record_blocked(bed, clinical_record);
         }
 
-        // 3) Children under 13 ONLY in unit 5
-        let unit = bed_number / 100;
-        if patient.age < 13 && unit != 5 {
-            return Err("Patients under 13 must be in unit 5".to_string());
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Validates and places a patient in a bed, returning the room beds it blocked (if any).
+    /// Does not record audit events: callers decide whether it was an admission or a move.
+    fn place_patient(
+        &mut self,
+        patient: &Patient,
+        bed_number: u32,
+    ) -> Result<Vec<u32>, HospitalError> {
+        self.can_admit(patient, bed_number)?;
+
+        // Admit the patient
+        self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(patient.clone());
+
+        // Block adjacent beds if needed (VIP or infectious)
+        let reasons = self.block_reasons_for(patient, bed_number);
+        Ok(self.block_room(bed_number, &reasons))
+    }
+
+    /// Checks whether a patient could be admitted to a bed right now.
+    /// Runs the same validation as `admit_patient` but changes nothing (e.g. to grey out beds).
+    pub fn can_admit(&self, patient: &Patient, bed_number: u32) -> Result<(), HospitalError> {
+        // 1) Check bed exists
+        let bed = self
+            .beds
+            .get(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+
+        // 2) Check bed is available (a reservation only holds it for its own patient)
+        if !self.is_free_for(bed, patient.clinical_record_number) {
+            return Err(HospitalError::BedNotAvailable(bed_number));
         }
 
-        // 4) Compatibility with roommate (if exists)
-        let roommate_bed_number = Self::roommate_of(bed_number);
-        if let Some(roommate_bed) = self.beds.get(&roommate_bed_number) {
+        // 3) Children (under 13 by default) ONLY in unit 5, and gendered units
+        self.check_unit(patient, bed_number)?;
+        self.check_capabilities(patient, bed_number)?;
+
+        // 4) Compatibility with every roommate (if any)
+        for roommate_bed_number in self.other_room_members(bed_number) {
+            let roommate_bed = &self.beds[&roommate_bed_number];
             if let BedState::Occupied(roommate) = &roommate_bed.state {
-                // Same gender rule
-                if patient.gender != roommate.gender {
-                    return Err("Roommates must have the same gender".to_string());
-                }
-                // Under 16 can only share with under 16
-                if (patient.age < 16) != (roommate.age < 16) {
-                    return Err(
-                        "Patients under 16 can only share with other under-16 patients".to_string(),
-                    );
-                }
-                // Cannot share with infectious or VIP patients
-                if roommate.is_infected || roommate.is_vip {
-                    return Err("Cannot share a room with an infectious or VIP patient".to_string());
-                }
+                self.check_occupant(patient, roommate_bed_number, roommate)?;
             }
-            // If new patient is infectious or VIP, adjacent bed must be free to block it
-            if (patient.is_infected || patient.is_vip) && !roommate_bed.is_available() {
-                return Err(
-                    "Patient requires the adjacent bed to be blocked, but it is not free"
-                        .to_string(),
-                );
+            // If new patient is infectious or VIP, adjacent beds must be free to block them
+            if self.needs_own_room(patient, bed_number) && !roommate_bed.is_available() {
+                return Err(HospitalError::AdjacentBedNotFree);
             }
         }
 
-        // 5) Admit the patient
-        self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(patient.clone());
+        Ok(())
+    }
 
-        // 6) Block adjacent bed if needed (VIP or infectious)
-        if patient.is_infected || patient.is_vip {
-            if let Some(roommate_bed) = self.beds.get_mut(&roommate_bed_number) {
-                if roommate_bed.is_available() {
-                    roommate_bed.state = BedState::Blocked;
+    /// Soft advisories for admitting a patient to a bed, on top of the hard rules of
+    /// `can_admit`. Children get a warning for each physically adjacent bed (the previous or
+    /// next bed number, in another room) tied to an infectious or isolated patient.
+    pub fn admission_warnings(&self, patient: &Patient, bed_number: u32) -> Vec<AdmissionWarning> {
+        if !self.rules.is_pediatric(patient) || !self.beds.contains_key(&bed_number) {
+            return Vec::new();
+        }
+        let room = self.room_members(bed_number);
+        let neighbours = [bed_number.checked_sub(1), bed_number.checked_add(1)];
+
+        let mut warnings = Vec::new();
+        for neighbour in neighbours.into_iter().flatten() {
+            if room.contains(&neighbour) {
+                continue; // Same room: the hard rules already cover it
+            }
+            match self.beds.get(&neighbour).map(|bed| &bed.state) {
+                Some(BedState::Occupied(p))
+                    if p.is_infected
+                        || p.requires_isolation
+                        || self.is_unit_isolated(self.unit_of(neighbour)) =>
+                {
+                    warnings.push(AdmissionWarning::AdjacentToInfectious {
+                        bed: neighbour,
+                        clinical_record: p.clinical_record_number,
+                    });
+                }
+                Some(BedState::Blocked { reasons }) => {
+                    for reason in reasons {
+                        if let BlockReason::AdjacentInfected(crn)
+                        | BlockReason::AdjacentIsolation(crn) = reason
+                        {
+                            warnings.push(AdmissionWarning::AdjacentToIsolationBlock {
+                                bed: neighbour,
+                                clinical_record: *crn,
+                            });
+                        }
+                    }
                 }
+                _ => {}
             }
         }
-
-        Ok(())
+        warnings
     }
 
-    /// Moves a patient from current bed to another empty bed (with simple rollback if fails)
+    /// Moves a patient from current bed to another empty bed (with simple rollback if fails).
+    /// Moving a patient to the bed they are already in succeeds without changing anything.
+    /// An unknown patient is always `PatientNotFound`, whatever the destination; any other
+    /// error is about the destination, and the patient stays where they were.
     pub fn move_patient(
         &mut self,
         clinical_record: u32,
-        new_bed_number: u16,
-    ) -> Result<(), String> {
-        // Find the patient
-        let (mut current_bed_number, mut patient_opt) = (0u16, None);
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record {
-                    current_bed_number = *bed_num;
-                    patient_opt = Some(p.clone());
-                    break;
-                }
-            }
+        new_bed_number: u32,
+    ) -> Result<(), HospitalError> {
+        // Find the patient before touching anything: an unknown patient changes nothing
+        let (current_bed_number, patient) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+
+        // Already there: nothing to do (no events, admission time untouched)
+        if new_bed_number == current_bed_number {
+            return Ok(());
         }
-        let patient = patient_opt.ok_or("Patient not found".to_string())?;
 
-        // Remember current bed's roommate (to unblock if needed)
-        let old_roommate_number = Self::roommate_of(current_bed_number);
+        // Remember the origin room as it is (for rollback)
+        let origin_room: Vec<(u32, BedState)> = self
+            .other_room_members(current_bed_number)
+            .into_iter()
+            .map(|n| (n, self.beds[&n].state.clone()))
+            .collect();
 
         // Free the origin bed
         self.beds.get_mut(&current_bed_number).unwrap().state = BedState::Vacant;
 
-        // If patient was VIP or infectious, roommate might have been blocked -> try to unblock
-        if patient.is_infected || patient.is_vip {
-            if let Some(old_rm) = self.beds.get_mut(&old_roommate_number) {
-                if old_rm.is_blocked() {
-                    old_rm.state = BedState::Vacant;
-                }
-            }
-        }
+        // If patient was VIP or infectious, roommates might have been blocked -> unblock them
+        let unblocked = self.unblock_room(current_bed_number, |r| {
+            r.clinical_record() == Some(clinical_record)
+        });
 
-        // Try to admit in destination
-        let result = self.admit_patient(patient.clone(), new_bed_number);
+        // Try to place in destination
+        let result = self.place_patient(&patient, new_bed_number);
 
         // If fails, rollback to original bed
-        if result.is_err() {
-            self.beds.get_mut(&current_bed_number).unwrap().state =
-                BedState::Occupied(patient.clone());
-            // Re-block if needed
-            if patient.is_infected || patient.is_vip {
-                if let Some(old_rm) = self.beds.get_mut(&old_roommate_number) {
-                    if old_rm.is_available() {
-                        old_rm.state = BedState::Blocked;
-                    }
+        let blocked = match result {
+            Ok(blocked) => blocked,
+            Err(e) => {
+                self.beds.get_mut(&current_bed_number).unwrap().state =
+                    BedState::Occupied(patient.clone());
+                // Restore the origin room's blocks
+                for (n, state) in origin_room {
+                    self.beds.get_mut(&n).unwrap().state = state;
                 }
+                return Err(e);
             }
-            return result;
+        };
+
+        self.record(AuditEvent::Moved {
+            clinical_record,
+            from: current_bed_number,
+            to: new_bed_number,
+            at: self.now(),
+        });
+        for bed in unblocked {
+            self.record_unblocked(bed, clinical_record);
+        }
+        for bed in blocked {
+            self.record_blocked(bed, clinical_record);
         }
 
+        self.debug_assert_invariants();
         Ok(())
     }
 
-    /// Switches beds between two patients (checks rules; if something fails, nothing changes)
+    /// Applies a sequence of moves (patient, destination bed) in order, e.g. through a
+    /// temporary bed to get a patient into a bed that is occupied at first. All or nothing:
+    /// if one move fails, the beds are left exactly as they were, nobody is told about the
+    /// moves already made, and its error is returned.
+    pub fn chain_move(&mut self, moves: &[(u32, u32)]) -> Result<(), HospitalError> {
+        self.atomically(|h| {
+            moves
+                .iter()
+                .try_for_each(|&(clinical_record, to)| h.move_patient(clinical_record, to))
+        })
+    }
+
+    /// Admits a patient to the recommended bed, preferably in `preferred_unit`, and returns the
+    /// bed chosen. Falls back to any unit if the preferred one has no valid bed; the pediatric
+    /// rule always applies.
+    pub fn admit_to_best(
+        &mut self,
+        patient: &Patient,
+        preferred_unit: Option<u16>,
+    ) -> Result<u32, HospitalError> {
+        let bed_number = preferred_unit
+            .and_then(|unit| self.recommend_bed_where(patient, |b| self.unit_of(b) == unit))
+            .or_else(|| self.recommend_bed(patient))
+            .ok_or(HospitalError::NoCompatibleBed)?;
+
+        self.admit_patient(patient, bed_number)?;
+        Ok(bed_number)
+    }
+
+    /// Admits a patient to `preferred_bed` if the rules allow it, otherwise to the bed
+    /// `recommend_bed` picks, and returns the bed used. Only failures tied to that bed
+    /// (taken, blocked, wrong unit, incompatible roommate) fall back; a bed that doesn't
+    /// exist or a patient already admitted is still an error, as is having no valid bed at
+    /// all (`NoCompatibleBed`).
+    pub fn admit_patient_or_alternate(
+        &mut self,
+        patient: &Patient,
+        preferred_bed: u32,
+    ) -> Result<u32, HospitalError> {
+        match self.admit_patient(patient, preferred_bed) {
+            Ok(()) => Ok(preferred_bed),
+            Err(e @ (HospitalError::BedNotFound(_) | HospitalError::PatientAlreadyAdmitted(_))) => {
+                Err(e)
+            }
+            Err(_) => self.admit_to_best(patient, None),
+        }
+    }
+
+    /// Moves a patient to the bed `recommend_bed` picks for them (never their current bed) and
+    /// returns it. Errors with `NoCompatibleBed` if there is none; the patient stays put.
+    pub fn move_patient_to_best(&mut self, clinical_record: u32) -> Result<u32, HospitalError> {
+        let (current_bed_number, patient) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+
+        // Recommend as if the patient had already left (their own blocks and their presence
+        // shouldn't count), then put the room back as it was
+        let room: Vec<(u32, BedState)> = self
+            .room_members(current_bed_number)
+            .into_iter()
+            .map(|n| (n, self.beds[&n].state.clone()))
+            .collect();
+        self.beds.get_mut(&current_bed_number).unwrap().state = BedState::Vacant;
+        self.unblock_room(current_bed_number, |r| {
+            r.clinical_record() == Some(clinical_record)
+        });
+        let best = self.recommend_bed_where(&patient, |b| b != current_bed_number);
+        for (n, state) in room {
+            self.beds.get_mut(&n).unwrap().state = state;
+        }
+
+        let destination = best.ok_or(HospitalError::NoCompatibleBed)?;
+        self.move_patient(clinical_record, destination)?;
+        Ok(destination)
+    }
+
+    /// Switches beds between two patients (checks rules; if something fails, nothing changes).
+    /// Adjacent blocking is recomputed for both beds, so a VIP/infectious patient's block
+    /// follows them to their new bed. Switching a patient with themselves does nothing.
     pub fn switch_patients(
         &mut self,
         clinical_record1: u32,
         clinical_record2: u32,
-    ) -> Result<(), String> {
+    ) -> Result<(), HospitalError> {
         // Find both patients
-        let (mut bed1_number, mut p1) = (0u16, None);
-        let (mut bed2_number, mut p2) = (0u16, None);
+        let (bed1_number, p1) = self
+            .find_patient_info(clinical_record1)
+            .ok_or(HospitalError::PatientNotFound(clinical_record1))?;
+        if clinical_record1 == clinical_record2 {
+            return Ok(()); // Already in each other's bed
+        }
+        let (bed2_number, p2) = self
+            .find_patient_info(clinical_record2)
+            .ok_or(HospitalError::PatientNotFound(clinical_record2))?;
 
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record1 {
-                    bed1_number = *bed_num;
-                    p1 = Some(p.clone());
-                } else if p.clinical_record_number == clinical_record2 {
-                    bed2_number = *bed_num;
-                    p2 = Some(p.clone());
+        // Rule: children only in the pediatric unit, and gendered units. Neither patient
+        // counts towards a gender cap: each one's place is taken by the other
+        let swapped = [clinical_record1, clinical_record2];
+        self.check_unit_for(p1.gender, p1.age, &swapped, bed2_number)?;
+        self.check_unit_for(p2.gender, p2.age, &swapped, bed1_number)?;
+        self.check_capabilities(&p1, bed2_number)?;
+        self.check_capabilities(&p2, bed1_number)?;
+
+        // Compatibility with destination roommates (if they're not roommates to each other)
+        let roommates1 = self.other_room_members(bed1_number);
+        let roommates2 = self.other_room_members(bed2_number);
+
+        if !roommates1.contains(&bed2_number) {
+            for (incoming, roommates) in [(&p2, &roommates1), (&p1, &roommates2)] {
+                for number in roommates {
+                    if let BedState::Occupied(rm) = &self.beds[number].state {
+                        self.check_occupant(incoming, *number, rm)?;
+                    }
                 }
             }
         }
 
-        let p1 = p1.ok_or("First patient not found".to_string())?;
-        let p2 = p2.ok_or("Second patient not found".to_string())?;
-
-        // Rule: children under 13 only in unit 5
-        if p1.age < 13 && (bed2_number / 100) != 5 {
-            return Err("Cannot move a patient under 13 outside unit 5".to_string());
-        }
-        if p2.age < 13 && (bed1_number / 100) != 5 {
-            return Err("Cannot move a patient under 13 outside unit 5".to_string());
+        // Build the proposed state of every affected bed (both beds and their roommates)
+        let mut proposed: HashMap<u32, BedState> = HashMap::new();
+        for number in roommates1.iter().chain(&roommates2) {
+            proposed.insert(*number, self.beds[number].state.clone());
         }
+        // Whole patients swap places: flags (infectious, VIP...) travel with the person, and
+        // admission times are kept by CRN, never by bed
+        proposed.insert(bed1_number, BedState::Occupied(p2.clone()));
+        proposed.insert(bed2_number, BedState::Occupied(p1.clone()));
 
-        // Compatibility with destination roommates (if they're not roommates to each other)
-        let roommate1_number = Self::roommate_of(bed1_number);
-        let roommate2_number = Self::roommate_of(bed2_number);
-
-        if bed2_number != roommate1_number {
-            if let Some(r1) = self.beds.get(&roommate1_number) {
-                if let BedState::Occupied(rm) = &r1.state {
-                    if p2.gender != rm.gender {
-                        return Err("Switch would violate gender rule".to_string());
-                    }
-                    if (p2.age < 16) != (rm.age < 16) {
-                        return Err("Switch would violate age rule".to_string());
-                    }
-                    if rm.is_infected || rm.is_vip {
-                        return Err(
-                            "Switch would violate rule: cannot share with infectious/VIP"
-                                .to_string(),
-                        );
-                    }
+        // Recompute adjacent blocking: drop both patients' block reasons, then add them
+        // back in the rooms they end up in
+        for state in proposed.values_mut() {
+            state.remove_block_reasons(|r| {
+                r.clinical_record()
+                    .is_some_and(|crn| swapped.contains(&crn))
+            });
+        }
+        for (roommates, incoming, destination) in [
+            (&roommates1, &p2, bed1_number),
+            (&roommates2, &p1, bed2_number),
+        ] {
+            let reasons = self.block_reasons_for(incoming, destination);
+            if reasons.is_empty() {
+                continue;
+            }
+            for roommate_number in roommates {
+                // Swapping two roommates doesn't change who shares the room
+                if *roommate_number == bed1_number || *roommate_number == bed2_number {
+                    continue;
                 }
+                let state = proposed.get_mut(roommate_number).unwrap();
+                if matches!(state, BedState::Occupied(_)) {
+                    return Err(HospitalError::AdjacentBedNotFree);
+                }
+                state.add_block_reasons(&reasons);
             }
         }
-        if bed1_number != roommate2_number {
-            if let Some(r2) = self.beds.get(&roommate2_number) {
-                if let BedState::Occupied(rm) = &r2.state {
-                    if p1.gender != rm.gender {
-                        return Err("Switch would violate gender rule".to_string());
-                    }
-                    if (p1.age < 16) != (rm.age < 16) {
-                        return Err("Switch would violate age rule".to_string());
-                    }
-                    if rm.is_infected || rm.is_vip {
-                        return Err(
-                            "Switch would violate rule: cannot share with infectious/VIP"
-                                .to_string(),
-                        );
-                    }
+
+        // Work out which beds changed between vacant and blocked (for the audit log)
+        let mut blocked = Vec::new();
+        let mut unblocked = Vec::new();
+        let first_patient =
+            |reasons: &[BlockReason]| reasons.iter().find_map(BlockReason::clinical_record);
+        for (number, after) in &proposed {
+            match (&self.beds[number].state, after) {
+                (BedState::Blocked { reasons }, BedState::Vacant) => {
+                    unblocked.extend(first_patient(reasons).map(|crn| (*number, crn)))
+                }
+                (BedState::Vacant, BedState::Blocked { reasons }) => {
+                    blocked.extend(first_patient(reasons).map(|crn| (*number, crn)))
                 }
+                _ => {}
             }
         }
+        blocked.sort_unstable();
+        unblocked.sort_unstable();
+
+        // Everything validated: commit the whole proposed state at once
+        for (number, state) in proposed {
+            self.beds.get_mut(&number).unwrap().state = state;
+        }
 
-        // Perform the switch
-        self.beds.get_mut(&bed1_number).unwrap().state = BedState::Occupied(p2);
-        self.beds.get_mut(&bed2_number).unwrap().state = BedState::Occupied(p1);
+        self.record(AuditEvent::Switched {
+            clinical_record1,
+            bed1: bed2_number,
+            clinical_record2,
+            bed2: bed1_number,
+            at: self.now(),
+        });
+        for (bed, clinical_record) in unblocked {
+            self.record_unblocked(bed, clinical_record);
+        }
+        for (bed, clinical_record) in blocked {
+            self.record_blocked(bed, clinical_record);
+        }
 
+        self.debug_assert_invariants();
         Ok(())
     }
 
-    /// Marks or unmarks a patient as VIP (if marking VIP, moves roommate if present)
-    pub fn set_patient_vip(&mut self, clinical_record: u32, is_vip: bool) -> Result<(), String> {
+    /// Marks or unmarks a patient as VIP (if marking VIP, moves roommate if present).
+    /// Returns the roommate's new bed (the first one relocated, in larger rooms)
+    pub fn set_patient_vip(
+        &mut self,
+        clinical_record: u32,
+        is_vip: bool,
+    ) -> Result<Option<u32>, HospitalError> {
         // Find the patient
-        let (mut bed_number, mut patient) = (0u16, None);
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record {
-                    bed_number = *bed_num;
-                    patient = Some(p.clone());
-                    break;
-                }
-            }
-        }
-        let mut p = patient.ok_or("Patient not found".to_string())?;
+        let (bed_number, mut p) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
         if p.is_vip == is_vip {
-            return Ok(()); // No changes needed
+            return Ok(None); // No changes needed
         }
 
-        // Apply and save
+        // Handle adjacent beds. The flag is only saved once the roommates are out, so a
+        // failed relocation leaves the patient exactly as they were.
         p.is_vip = is_vip;
-        self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p.clone());
+        let mut relocated_to = None;
+        if is_vip && !self.rules.vip_blocks_adjacent {
+            // VIPs don't block their room under this policy: roommates stay where they are
+            self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p);
+            self.record_set_vip(clinical_record, bed_number, is_vip);
+        } else if is_vip {
+            // If there are roommates, move them first; if one can't be placed, nobody has
+            // moved and nothing is logged
+            relocated_to = self.atomically(|h| {
+                let relocated_to = h.relocate_roommates(bed_number)?;
+                h.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p);
 
-        // Handle adjacent bed
-        let roommate_number = Self::roommate_of(bed_number);
-        if is_vip {
-            // If there's a roommate, move them first
-            if let Some(rm) = self.beds.get(&roommate_number) {
-                if let BedState::Occupied(roommate) = &rm.state {
-                    let roommate_crn = roommate.clinical_record_number;
-                    let candidates = self.get_available_beds_for_patient(&roommate.clone());
-                    if let Some(dest) = candidates.first() {
-                        self.move_patient(roommate_crn, *dest)?;
-                    } else {
-                        return Err("No available bed to relocate roommate".to_string());
-                    }
+                // Now block the adjacent beds
+                let newly_blocked =
+                    h.block_room(bed_number, &[BlockReason::AdjacentVip(clinical_record)]);
+                h.record_set_vip(clinical_record, bed_number, is_vip);
+                for bed in newly_blocked {
+                    h.record_blocked(bed, clinical_record);
                 }
-            }
-            // Now block the adjacent bed
-            if let Some(rm) = self.beds.get_mut(&roommate_number) {
-                rm.state = BedState::Blocked;
-            }
+                Ok(relocated_to)
+            })?;
         } else {
-            // If no longer VIP and not infectious, unblock
-            if !p.is_infected {
-                if let Some(rm) = self.beds.get_mut(&roommate_number) {
-                    if rm.is_blocked() {
-                        rm.state = BedState::Vacant;
-                    }
-                }
+            self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p);
+
+            // If no longer VIP, release the VIP blocks (infection blocks stay)
+            let unblocked = self.unblock_room(bed_number, |r| {
+                *r == BlockReason::AdjacentVip(clinical_record)
+            });
+            self.record_set_vip(clinical_record, bed_number, is_vip);
+            for bed in unblocked {
+                self.record_unblocked(bed, clinical_record);
             }
         }
-        Ok(())
+        self.debug_assert_invariants();
+        Ok(relocated_to)
     }
 
-    /// Marks a patient as infectious (if there's a roommate, they must be moved; if no space, error)
-    pub fn mark_patient_as_infected(&mut self, clinical_record: u32) -> Result<(), String> {
-        // Find the patient
-        let (mut bed_number, mut patient) = (0u16, None);
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record {
-                    bed_number = *bed_num;
-                    patient = Some(p.clone());
-                    break;
-                }
+    /// Clears VIP status on every VIP patient (e.g. to free capacity during a surge), as
+    /// `set_patient_vip(.., false)` would. Returns (CRN, bed) for every bed freed, sorted by
+    /// the patients' beds; beds still blocked for another reason (infection) stay blocked.
+    pub fn revoke_all_vip(&mut self) -> Vec<(u32, u32)> {
+        let vips: Vec<(u32, Vec<u32>)> = self
+            .iter_beds()
+            .filter_map(|(bed_number, bed)| match &bed.state {
+                BedState::Occupied(p) if p.is_vip => Some((p.clinical_record_number, bed_number)),
+                _ => None,
+            })
+            .map(|(crn, bed_number)| {
+                let blocked = self
+                    .other_room_members(bed_number)
+                    .into_iter()
+                    .filter(|n| {
+                        self.beds[n]
+                            .block_reasons()
+                            .contains(&BlockReason::AdjacentVip(crn))
+                    })
+                    .collect();
+                (crn, blocked)
+            })
+            .collect();
+
+        let mut freed = Vec::new();
+        for (crn, blocked) in vips {
+            self.set_patient_vip(crn, false)
+                .expect("VIP patient is admitted");
+            freed.extend(
+                blocked
+                    .into_iter()
+                    .filter(|n| self.beds[n].is_available())
+                    .map(|n| (crn, n)),
+            );
+        }
+        freed
+    }
+
+    /// Gives a bed blocked only for a VIP to `new_patient`: the VIP is demoted (as with
+    /// `set_patient_vip(.., false)`) and the patient admitted there. Beds blocked for any other
+    /// reason (infection, isolation, staff) can't be preempted (`BedNotAvailable`). If the
+    /// patient can't be admitted, the VIP keeps their status and blocks, nothing is logged,
+    /// and the error is returned.
+    pub fn preempt_block(
+        &mut self,
+        blocked_bed: u32,
+        new_patient: &Patient,
+    ) -> Result<(), HospitalError> {
+        let bed = self
+            .beds
+            .get(&blocked_bed)
+            .ok_or(HospitalError::BedNotFound(blocked_bed))?;
+        let vip = match bed.block_reasons() {
+            [BlockReason::AdjacentVip(crn), rest @ ..]
+                if rest.iter().all(|r| *r == BlockReason::AdjacentVip(*crn)) =>
+            {
+                *crn
             }
+            _ => return Err(HospitalError::BedNotAvailable(blocked_bed)),
+        };
+        if let Some((bed_number, _)) = self.find_patient_info(new_patient.clinical_record_number) {
+            return Err(HospitalError::PatientAlreadyAdmitted(bed_number));
         }
-        let mut p = patient.ok_or("Patient not found".to_string())?;
+
+        self.atomically(|h| {
+            h.set_patient_vip(vip, false)?;
+            h.admit_patient(new_patient, blocked_bed)
+        })
+    }
+
+    /// Marks a patient as infectious (if there's a roommate, they must be moved; if no space, error
+    /// or waitlist them, depending on the relocation failure policy).
+    /// Returns the roommate's new bed (the first one relocated, in larger rooms)
+    pub fn mark_patient_as_infected(
+        &mut self,
+        clinical_record: u32,
+    ) -> Result<Option<u32>, HospitalError> {
+        // Find the patient
+        let (bed_number, mut p) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
         if p.is_infected {
-            return Ok(());
+            return Ok(None);
         }
         p.is_infected = true;
 
-        let roommate_number = Self::roommate_of(bed_number);
+        let relocated_to = self.atomically(|h| {
+            // If there are roommates, move them to another valid bed (outside this room); if
+            // one can't be placed, nobody has moved and nothing is logged
+            let relocated_to = h.relocate_roommates(bed_number)?;
 
-        // If there's a roommate, move them to another valid bed
-        let mut roommate_to_move: Option<Patient> = None;
-        if let Some(rm_bed) = self.beds.get(&roommate_number) {
-            if let BedState::Occupied(rm) = &rm_bed.state {
-                roommate_to_move = Some(rm.clone());
-            }
-        }
-        if let Some(roommate) = roommate_to_move {
-            // Find a compatible free bed
-            let candidates = self.get_available_beds_for_patient(&roommate);
-            if let Some(dest) = candidates.into_iter().next() {
-                self.move_patient(roommate.clinical_record_number, dest)?;
-            } else {
-                return Err("No available bed to move roommate".to_string());
-            }
-        }
+            // Save patient as infectious and block adjacent beds
+            h.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p);
+            let newly_blocked = h.block_room(
+                bed_number,
+                &[BlockReason::AdjacentInfected(clinical_record)],
+            );
 
-        // Save patient as infectious and block adjacent bed
-        self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p);
-        if let Some(rm_bed) = self.beds.get_mut(&roommate_number) {
-            if rm_bed.is_available() {
-                rm_bed.state = BedState::Blocked;
+            h.record(AuditEvent::MarkedInfected {
+                clinical_record,
+                bed: bed_number,
+                is_infected: true,
+                at: h.now(),
+            });
+            for bed in newly_blocked {
+                h.record_blocked(bed, clinical_record);
             }
-        }
+            Ok(relocated_to)
+        })?;
 
-        Ok(())
+        self.debug_assert_invariants();
+        Ok(relocated_to)
     }
 
     /// Unmarks a patient as infectious (if not VIP, adjacent bed can be unblocked)
-    pub fn unmark_patient_as_infected(&mut self, clinical_record: u32) -> Result<(), String> {
+    pub fn unmark_patient_as_infected(
+        &mut self,
+        clinical_record: u32,
+    ) -> Result<(), HospitalError> {
         // Find the patient
-        let (mut bed_number, mut patient) = (0u16, None);
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record {
-                    bed_number = *bed_num;
-                    patient = Some(p.clone());
-                    break;
-                }
-            }
-        }
-        let mut p = patient.ok_or("Patient not found".to_string())?;
+        let (bed_number, mut p) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
         if !p.is_infected {
             return Ok(());
         }
         p.is_infected = false;
         self.beds.get_mut(&bed_number).unwrap().state = BedState::Occupied(p.clone());
 
-        // If also not VIP -> unblock if it was blocked
-        if !p.is_vip {
-            let roommate_number = Self::roommate_of(bed_number);
-            if let Some(rm_bed) = self.beds.get_mut(&roommate_number) {
-                if rm_bed.is_blocked() {
-                    rm_bed.state = BedState::Vacant;
-                }
-            }
+        self.record(AuditEvent::MarkedInfected {
+            clinical_record,
+            bed: bed_number,
+            is_infected: false,
+            at: self.now(),
+        });
+
+        // Release the infection blocks (VIP blocks, if any, stay). In an isolation unit the
+        // patient is still treated as infectious, so the room stays blocked.
+        if self.is_unit_isolated(self.unit_of(bed_number)) {
+            self.debug_assert_invariants();
+            return Ok(());
         }
+        let unblocked = self.unblock_room(bed_number, |r| {
+            *r == BlockReason::AdjacentInfected(clinical_record)
+        });
+        for bed in unblocked {
+            self.record_unblocked(bed, clinical_record);
+        }
+        self.debug_assert_invariants();
         Ok(())
     }
 
-    /// Discharges a patient (bed becomes Vacant and adjacent blocking is recalculated if needed)
-    pub fn discharge_patient(&mut self, clinical_record: u32) -> Result<(), String> {
-        // Find the patient
-        let (mut bed_number, mut patient) = (0u16, None);
-        for (bed_num, bed) in &self.beds {
-            if let BedState::Occupied(p) = &bed.state {
-                if p.clinical_record_number == clinical_record {
-                    bed_number = *bed_num;
-                    patient = Some(p.clone());
-                    break;
-                }
+    /// Gives an admitted patient their whole room: roommates are relocated (all or nothing)
+    /// and the other beds are blocked, so none of them is offered. The room follows the
+    /// patient if they're moved. Returns the patient's bed.
+    pub fn assign_private_room(&mut self, clinical_record: u32) -> Result<u32, HospitalError> {
+        let (bed_number, _) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+        if self.private_rooms.contains(&clinical_record) {
+            return Ok(bed_number);
+        }
+
+        self.atomically(|h| {
+            h.relocate_roommates(bed_number)?;
+            h.private_rooms.insert(clinical_record);
+            let newly_blocked =
+                h.block_room(bed_number, &[BlockReason::PrivateRoom(clinical_record)]);
+            for bed in newly_blocked {
+                h.record_blocked(bed, clinical_record);
+            }
+            Ok(())
+        })?;
+        self.debug_assert_invariants();
+        Ok(bed_number)
+    }
+
+    /// Undoes `assign_private_room`: the beds held for the patient are released
+    /// (blocks for other reasons, e.g. infection, stay)
+    pub fn release_private_room(&mut self, clinical_record: u32) -> Result<(), HospitalError> {
+        let (bed_number, _) = self
+            .find_patient_info(clinical_record)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+        if !self.private_rooms.remove(&clinical_record) {
+            return Ok(());
+        }
+
+        let unblocked = self.unblock_room(bed_number, |r| {
+            *r == BlockReason::PrivateRoom(clinical_record)
+        });
+        for bed in unblocked {
+            self.record_unblocked(bed, clinical_record);
+        }
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Runs a `Command` through the matching operation (same rules, events and errors)
+    pub fn apply(&mut self, command: Command) -> Result<CommandOutcome, HospitalError> {
+        match command {
+            Command::Admit { patient, bed } => self
+                .admit_patient(&patient, bed)
+                .map(|_| CommandOutcome::Done),
+            Command::Move {
+                clinical_record,
+                to,
+            } => self
+                .move_patient(clinical_record, to)
+                .map(|_| CommandOutcome::Done),
+            Command::Switch {
+                clinical_record1,
+                clinical_record2,
+            } => self
+                .switch_patients(clinical_record1, clinical_record2)
+                .map(|_| CommandOutcome::Done),
+            Command::Discharge { clinical_record } => self
+                .discharge_patient(clinical_record)
+                .map(CommandOutcome::Discharged),
+            Command::SetVip {
+                clinical_record,
+                is_vip,
+            } => self
+                .set_patient_vip(clinical_record, is_vip)
+                .map(CommandOutcome::Relocated),
+            Command::MarkInfected { clinical_record } => self
+                .mark_patient_as_infected(clinical_record)
+                .map(CommandOutcome::Relocated),
+            Command::UnmarkInfected { clinical_record } => self
+                .unmark_patient_as_infected(clinical_record)
+                .map(|_| CommandOutcome::Done),
+            Command::SetMaintenance { bed, on } => {
+                self.set_maintenance(bed, on).map(|_| CommandOutcome::Done)
             }
         }
-        let p = patient.ok_or("Patient not found".to_string())?;
+    }
+
+    /// Attaches a tag to an admitted patient (no-op if they already have it). Tags are part of
+    /// the patient, so they follow them through moves and switches.
+    pub fn add_tag(&mut self, clinical_record: u32, tag: &str) -> Result<(), HospitalError> {
+        let patient = self.patient_mut(clinical_record)?;
+        if !patient.tags.iter().any(|t| t == tag) {
+            patient.tags.push(tag.to_string());
+        }
+        Ok(())
+    }
+
+    /// Removes a tag from an admitted patient (no-op if they don't have it)
+    pub fn remove_tag(&mut self, clinical_record: u32, tag: &str) -> Result<(), HospitalError> {
+        self.patient_mut(clinical_record)?.tags.retain(|t| t != tag);
+        Ok(())
+    }
+
+    /// The admitted patient with this CRN, for changes that don't affect any rule
+    fn patient_mut(&mut self, clinical_record: u32) -> Result<&mut Patient, HospitalError> {
+        self.beds
+            .values_mut()
+            .find_map(|bed| match &mut bed.state {
+                BedState::Occupied(p) if p.clinical_record_number == clinical_record => Some(p),
+                _ => None,
+            })
+            .ok_or(HospitalError::PatientNotFound(clinical_record))
+    }
+
+    /// Discharges a patient (bed becomes Vacant and adjacent blocking is recalculated if needed)
+    /// and returns the beds that changed
+    pub fn discharge_patient(
+        &mut self,
+        clinical_record: u32,
+    ) -> Result<DischargeOutcome, HospitalError> {
+        // Find the patient
+        let bed_number = self
+            .find_patient_info(clinical_record)
+            .map(|(bed_number, _)| bed_number)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+
+        let (_, unblocked) = self.vacate_bed(bed_number)?;
+        Ok(DischargeOutcome {
+            freed_bed: bed_number,
+            unblocked_bed: unblocked.first().copied(),
+        })
+    }
+
+    /// Like `discharge_patient`, but a patient who isn't admitted (e.g. already discharged)
+    /// isn't an error. Returns whether someone was actually discharged.
+    pub fn discharge_patient_if_present(&mut self, clinical_record: u32) -> bool {
+        self.discharge_patient(clinical_record).is_ok()
+    }
+
+    /// Discharges whoever occupies `bed_number` and returns that patient
+    /// (errors if the bed doesn't exist, or is vacant/blocked)
+    pub fn discharge_bed(&mut self, bed_number: u32) -> Result<Patient, HospitalError> {
+        self.vacate_bed(bed_number).map(|(p, _)| p)
+    }
+
+    /// Frees an occupied bed, releasing the beds blocked for its patient.
+    /// Returns the patient and the beds that were unblocked.
+    fn vacate_bed(&mut self, bed_number: u32) -> Result<(Patient, Vec<u32>), HospitalError> {
+        let bed = self
+            .beds
+            .get_mut(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+        let p = match &bed.state {
+            BedState::Occupied(p) => p.clone(),
+            _ => return Err(HospitalError::BedNotOccupied(bed_number)),
+        };
+        let clinical_record = p.clinical_record_number;
 
         // Free the bed
-        self.beds.get_mut(&bed_number).unwrap().state = BedState::Vacant;
-
-        // If patient was VIP or infectious, adjacent bed might have been blocked: unblock it
-        if p.is_infected || p.is_vip {
-            let roommate_number = Self::roommate_of(bed_number);
-            if let Some(rm_bed) = self.beds.get_mut(&roommate_number) {
-                if rm_bed.is_blocked() {
-                    rm_bed.state = BedState::Vacant;
+        bed.state = BedState::Vacant;
+        self.private_rooms.remove(&clinical_record);
+        self.admission_times.remove(&clinical_record);
+        self.record(AuditEvent::Discharged {
+            clinical_record,
+            bed: bed_number,
+            at: self.now(),
+        });
+
+        // If patient was VIP or infectious, adjacent beds might have been blocked: unblock them
+        let unblocked =
+            self.unblock_room(bed_number, |r| r.clinical_record() == Some(clinical_record));
+        for &bed in &unblocked {
+            self.record_unblocked(bed, clinical_record);
+        }
+
+        self.debug_assert_invariants();
+        Ok((p, unblocked))
+    }
+
+    /// Discharges a patient to send them to another hospital and returns them
+    /// (admit them there with `admit_patient`, or use `transfer`)
+    pub fn transfer_out(&mut self, clinical_record: u32) -> Result<Patient, HospitalError> {
+        let bed_number = self
+            .find_patient_info(clinical_record)
+            .map(|(bed_number, _)| bed_number)
+            .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+
+        self.discharge_bed(bed_number)
+    }
+
+    /// Discharges every patient in `unit` (e.g. an evacuation), releasing the beds they
+    /// blocked. Returns the discharged CRNs in bed order; empty if nobody was there.
+    pub fn discharge_unit(&mut self, unit: u16) -> Vec<u32> {
+        let occupied = self.beds_matching(|bed, n| {
+            self.unit_of(n) == unit && matches!(bed.state, BedState::Occupied(_))
+        });
+
+        occupied
+            .into_iter()
+            .map(|bed_number| {
+                self.discharge_bed(bed_number)
+                    .expect("bed was occupied")
+                    .clinical_record_number
+            })
+            .collect()
+    }
+
+    /// Moves every roommate of `bed_number` to a valid bed outside the room and returns the
+    /// first new bed. If one can't be placed, `NoBedForRoommate` is returned with the others
+    /// already moved: callers run it inside `atomically`, which puts them back. Under
+    /// `RelocationFailurePolicy::WaitlistRoommate`, that roommate is discharged to the
+    /// waitlist instead and the others keep their new beds.
+    fn relocate_roommates(&mut self, bed_number: u32) -> Result<Option<u32>, HospitalError> {
+        let roommates: Vec<(u32, Patient)> = self
+            .room_occupants(bed_number)
+            .into_iter()
+            .filter(|&(n, _)| n != bed_number)
+            .map(|(n, p)| (n, p.clone()))
+            .collect();
+
+        let mut first_destination = None;
+        for (origin, roommate) in roommates {
+            let crn = roommate.clinical_record_number;
+            let result = match self.relocation.choose(self, &roommate, bed_number) {
+                Some(dest) => self.move_patient(crn, dest).map(|_| dest),
+                None => Err(HospitalError::NoBedForRoommate),
+            };
+            match result {
+                Ok(dest) => {
+                    first_destination.get_or_insert(dest);
+                }
+                Err(_)
+                    if self.on_relocation_failure == RelocationFailurePolicy::WaitlistRoommate =>
+                {
+                    let roommate = self
+                        .discharge_bed(origin)
+                        .expect("roommate bed is occupied");
+                    self.add_to_waitlist(roommate);
                 }
+                Err(e) => return Err(e),
             }
         }
 
+        Ok(first_destination)
+    }
+
+    /// Discharges the patient in `bed_number` and takes the bed out of service for cleaning.
+    /// It goes back into service with `expire_maintenance` once `cleaning` has passed.
+    pub fn cycle_bed(&mut self, bed_number: u32, cleaning: Duration) -> Result<(), HospitalError> {
+        self.vacate_bed(bed_number)?;
+        self.set_maintenance(bed_number, true)?;
+        self.cleaning_until
+            .insert(bed_number, self.now() + cleaning);
         Ok(())
     }
 
-    // ----------------- Queries -----------------
+    /// Puts back into service every bed whose cleaning (`cycle_bed`) is over, and returns
+    /// them sorted
+    pub fn expire_maintenance(&mut self) -> Vec<u32> {
+        let now = self.now();
+        let mut done: Vec<u32> = self
+            .cleaning_until
+            .iter()
+            .filter(|&(_, &until)| until <= now)
+            .map(|(&bed_number, _)| bed_number)
+            .collect();
+        done.sort_unstable();
+        for &bed_number in &done {
+            self.set_maintenance(bed_number, false)
+                .expect("bed being cleaned is in maintenance");
+        }
+        done
+    }
+
+    /// Takes a bed out of service (`on: true`) or puts it back (`on: false`).
+    /// Occupied or blocked beds can't go into maintenance. When it comes back, the bed is
+    /// blocked again if a VIP/infectious patient is in the room.
+    pub fn set_maintenance(&mut self, bed_number: u32, on: bool) -> Result<(), HospitalError> {
+        let bed = self
+            .beds
+            .get(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+
+        match (&bed.state, on) {
+            (BedState::Maintenance, true) | (BedState::Vacant, false) => return Ok(()),
+            (BedState::Occupied(_), true) => return Err(HospitalError::BedOccupied(bed_number)),
+            (BedState::Blocked { .. } | BedState::Reserved { .. }, true) => {
+                return Err(HospitalError::BedNotAvailable(bed_number))
+            }
+            (BedState::Vacant, true) => {
+                self.beds.get_mut(&bed_number).unwrap().state = BedState::Maintenance;
+            }
+            (BedState::Maintenance, false) => {
+                self.cleaning_until.remove(&bed_number);
+                self.put_back_in_service(bed_number);
+            }
+            // Taking an occupied/blocked/reserved bed "out" of maintenance: nothing to do
+            (_, false) => return Ok(()),
+        }
+
+        self.record(AuditEvent::Maintenance {
+            bed: bed_number,
+            on,
+            at: self.now(),
+        });
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Blocks a free bed by hand (e.g. broken equipment). Only reasons not tied to a patient
+    /// (`BlockReason::Other`) are accepted (`NotAManualBlock` otherwise): those are never
+    /// released by discharges, only by `unblock_bed`. An already blocked bed gets the extra
+    /// reason; occupied or maintenance beds can't be blocked.
+    pub fn block_bed(&mut self, bed_number: u32, reason: BlockReason) -> Result<(), HospitalError> {
+        if reason.clinical_record().is_some() {
+            return Err(HospitalError::NotAManualBlock(reason));
+        }
+        let bed = self
+            .beds
+            .get_mut(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+
+        match &bed.state {
+            BedState::Occupied(_) => return Err(HospitalError::BedOccupied(bed_number)),
+            BedState::Maintenance | BedState::Reserved { .. } => {
+                return Err(HospitalError::BedNotAvailable(bed_number))
+            }
+            BedState::Blocked { reasons } if reasons.contains(&reason) => return Ok(()),
+            BedState::Vacant | BedState::Blocked { .. } => bed.state.add_block_reasons(&[reason]),
+        };
+
+        self.record(AuditEvent::ManualBlock {
+            bed: bed_number,
+            reason,
+            on: true,
+            at: self.now(),
+        });
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Lifts a block added with `block_bed`. The bed becomes vacant once no other reason is
+    /// left; nothing happens if the bed isn't blocked for that reason. Blocks tied to a
+    /// patient are lifted by the patient leaving, never by hand (`NotAManualBlock`).
+    pub fn unblock_bed(
+        &mut self,
+        bed_number: u32,
+        reason: BlockReason,
+    ) -> Result<(), HospitalError> {
+        if reason.clinical_record().is_some() {
+            return Err(HospitalError::NotAManualBlock(reason));
+        }
+        let bed = self
+            .beds
+            .get_mut(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+        if !bed.block_reasons().contains(&reason) {
+            return Ok(());
+        }
+
+        bed.state.remove_block_reasons(|r| *r == reason);
+        self.record(AuditEvent::ManualBlock {
+            bed: bed_number,
+            reason,
+            on: false,
+            at: self.now(),
+        });
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Holds a vacant bed for an incoming patient: nobody else can be admitted to it until
+    /// the reservation is cancelled or `hold` has passed (see `expire_reservations`; None
+    /// holds it until cancelled). Reserving the bed again for the same patient, or a bed
+    /// whose reservation has expired, replaces the reservation. The clinical record number
+    /// must be valid, and the patient can't be admitted already (`PatientAlreadyAdmitted`)
+    /// or hold another bed (`PatientAlreadyReserved`).
+    pub fn reserve_bed(
+        &mut self,
+        bed_number: u32,
+        clinical_record: u32,
+        hold: Option<Duration>,
+    ) -> Result<(), HospitalError> {
+        let until = hold.map(|hold| self.now() + hold);
+        self.reserve_bed_until(bed_number, clinical_record, until)
+    }
+
+    /// `reserve_bed` with the end of the hold as a point in time
+    fn reserve_bed_until(
+        &mut self,
+        bed_number: u32,
+        clinical_record: u32,
+        until: Option<SystemTime>,
+    ) -> Result<(), HospitalError> {
+        if !is_valid_clinical_record(clinical_record) {
+            return Err(HospitalError::InvalidClinicalRecord(clinical_record));
+        }
+        if let Some((existing_bed, _)) = self.find_patient_info(clinical_record) {
+            return Err(HospitalError::PatientAlreadyAdmitted(existing_bed));
+        }
+        // One reserved bed per patient (expired reservations don't count)
+        let now = self.now();
+        let held = self.beds.iter().find(|&(&n, bed)| {
+            n != bed_number
+                && matches!(bed.state, BedState::Reserved { clinical_record: crn, .. }
+                    if crn == clinical_record)
+                && !bed.state.is_expired_reservation(now)
+        });
+        if let Some((&held_bed, _)) = held {
+            return Err(HospitalError::PatientAlreadyReserved(held_bed));
+        }
+
+        let bed = self
+            .beds
+            .get(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+        match &bed.state {
+            BedState::Occupied(_) => return Err(HospitalError::BedOccupied(bed_number)),
+            _ if !self.is_free_for(bed, clinical_record) => {
+                return Err(HospitalError::BedNotAvailable(bed_number))
+            }
+            _ => {}
+        }
+
+        self.beds.get_mut(&bed_number).unwrap().state = BedState::Reserved {
+            clinical_record,
+            until,
+        };
+        self.record(AuditEvent::Reserved {
+            bed: bed_number,
+            clinical_record,
+            on: true,
+            at: self.now(),
+        });
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Cancels the reservation of a bed, which becomes vacant again (or blocked, if a
+    /// VIP/infectious patient is in the room). Nothing happens if the bed isn't reserved.
+    pub fn cancel_reservation(&mut self, bed_number: u32) -> Result<(), HospitalError> {
+        let bed = self
+            .beds
+            .get(&bed_number)
+            .ok_or(HospitalError::BedNotFound(bed_number))?;
+        let BedState::Reserved {
+            clinical_record, ..
+        } = bed.state
+        else {
+            return Ok(());
+        };
+
+        self.put_back_in_service(bed_number);
+        self.record(AuditEvent::Reserved {
+            bed: bed_number,
+            clinical_record,
+            on: false,
+            at: self.now(),
+        });
+        self.debug_assert_invariants();
+        Ok(())
+    }
+
+    /// Releases every reservation whose hold is over (by the hospital clock), and returns
+    /// those beds sorted
+    pub fn expire_reservations(&mut self) -> Vec<u32> {
+        let now = self.now();
+        let expired: Vec<u32> = self
+            .iter_beds()
+            .filter(|(_, bed)| bed.state.is_expired_reservation(now))
+            .map(|(bed_number, _)| bed_number)
+            .collect();
+        for &bed_number in &expired {
+            self.cancel_reservation(bed_number)
+                .expect("expired bed is reserved");
+        }
+        expired
+    }
+
+    /// Can a patient be put in this bed as far as its state goes? Vacant beds, beds reserved
+    /// for them and expired reservations qualify.
+    fn is_free_for(&self, bed: &Bed, clinical_record: u32) -> bool {
+        match bed.state {
+            BedState::Vacant => true,
+            BedState::Reserved {
+                clinical_record: holder,
+                ..
+            } => holder == clinical_record || bed.state.is_expired_reservation(self.now()),
+            _ => false,
+        }
+    }
+
+    /// Makes a bed vacant, blocked again if a VIP/infectious patient is in the room
+    fn put_back_in_service(&mut self, bed_number: u32) {
+        let mut reasons = Vec::new();
+        for number in self.other_room_members(bed_number) {
+            if let BedState::Occupied(p) = &self.beds[&number].state {
+                reasons.extend(self.block_reasons_for(p, number));
+            }
+        }
+        let bed = self.beds.get_mut(&bed_number).unwrap();
+        bed.state = BedState::Vacant;
+        bed.state.add_block_reasons(&reasons);
+    }
+
+    /// Adds block reasons to every free/blocked bed in the room of `bed_number`,
+    /// returning the ones that went from vacant to blocked
+    fn block_room(&mut self, bed_number: u32, reasons: &[BlockReason]) -> Vec<u32> {
+        let mut blocked = Vec::new();
+        for number in self.other_room_members(bed_number) {
+            let bed = self.beds.get_mut(&number).unwrap();
+            if bed.state.add_block_reasons(reasons) {
+                blocked.push(number);
+            }
+        }
+        blocked
+    }
+
+    /// Removes matching block reasons in the room of `bed_number`, returning the beds
+    /// that became vacant (a bed stays blocked while any other reason remains)
+    fn unblock_room(&mut self, bed_number: u32, remove: impl Fn(&BlockReason) -> bool) -> Vec<u32> {
+        let mut unblocked = Vec::new();
+        for number in self.other_room_members(bed_number) {
+            let bed = self.beds.get_mut(&number).unwrap();
+            if bed.state.remove_block_reasons(&remove) {
+                unblocked.push(number);
+            }
+        }
+        unblocked
+    }
+
+    /// Block reasons a patient in `bed_number` imposes on the rest of their room (none for
+    /// regular patients). Beds in an isolation unit count as infectious.
+    fn block_reasons_for(&self, patient: &Patient, bed_number: u32) -> Vec<BlockReason> {
+        let mut reasons = Vec::new();
+        if patient.is_vip && self.rules.vip_blocks_adjacent {
+            reasons.push(BlockReason::AdjacentVip(patient.clinical_record_number));
+        }
+        if patient.is_infected || self.is_unit_isolated(self.unit_of(bed_number)) {
+            reasons.push(BlockReason::AdjacentInfected(
+                patient.clinical_record_number,
+            ));
+        }
+        if patient.requires_isolation {
+            reasons.push(BlockReason::AdjacentIsolation(
+                patient.clinical_record_number,
+            ));
+        }
+        if self.private_rooms.contains(&patient.clinical_record_number) {
+            reasons.push(BlockReason::PrivateRoom(patient.clinical_record_number));
+        }
+        reasons
+    }
+
+    /// Unit-level rules: children only in the pediatric unit, gendered units only take their
+    /// gender, and gender caps
+    fn check_unit(&self, patient: &Patient, bed_number: u32) -> Result<(), HospitalError> {
+        self.check_unit_for(
+            patient.gender,
+            patient.age,
+            &[patient.clinical_record_number],
+            bed_number,
+        )
+    }
+
+    /// `check_unit` from a patient's attributes (the patients in `except` are left out of the
+    /// gender cap count)
+    fn check_unit_for(
+        &self,
+        gender: Gender,
+        age: u8,
+        except: &[u32],
+        bed_number: u32,
+    ) -> Result<(), HospitalError> {
+        let unit = self.unit_of(bed_number);
+        if !self.rules.allows_unit_for_age(age, unit) {
+            return Err(HospitalError::PediatricUnitRequired);
+        }
+        if self.unit_gender(unit).is_some_and(|g| g != gender) {
+            return Err(HospitalError::UnitGenderMismatch(unit));
+        }
+        if self.gender_cap_left(unit, gender, except) == Some(0) {
+            return Err(HospitalError::GenderCapReached(unit));
+        }
+        Ok(())
+    }
+
+    /// Could a regular patient (not VIP/infectious/isolated) of this gender and age share
+    /// `roommate`'s room? The same rules as `check_roommate`, from the attributes alone.
+    fn profile_fits_roommate(
+        &self,
+        gender: Gender,
+        age: u8,
+        roommate: &Patient,
+        roommate_bed: u32,
+    ) -> bool {
+        (gender == roommate.gender || self.is_family_room(roommate_bed))
+            && self.rules.is_minor_age(age) == self.rules.is_minor(roommate)
+            && !self.rules.needs_own_room(roommate)
+            && !self.is_unit_isolated(self.unit_of(roommate_bed))
+            && !self
+                .private_rooms
+                .contains(&roommate.clinical_record_number)
+    }
+
+    /// The bed has all the equipment the patient requires
+    fn check_capabilities(&self, patient: &Patient, bed_number: u32) -> Result<(), HospitalError> {
+        let bed = &self.beds[&bed_number];
+        match patient
+            .requirements
+            .iter()
+            .find(|c| !bed.capabilities.contains(c))
+        {
+            Some(&capability) => Err(HospitalError::MissingBedCapability {
+                bed: bed_number,
+                capability,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Roommate compatibility (`compatible_roommates`) plus the hospital-wide part: nobody
+    /// shares a room in a unit under isolation or a room held private
+    fn check_roommate(
+        &self,
+        patient: &Patient,
+        roommate: &Patient,
+        bed_number: u32,
+    ) -> Result<(), HospitalError> {
+        if self.is_family_room(bed_number) {
+            compatible_roommates_any_gender(patient, roommate, &self.rules)?;
+        } else {
+            compatible_roommates(patient, roommate, &self.rules)?;
+        }
+        if self.is_unit_isolated(self.unit_of(bed_number))
+            || self
+                .private_rooms
+                .contains(&roommate.clinical_record_number)
+        {
+            return Err(HospitalError::RoommateRequiresIsolation);
+        }
+        if self.private_rooms.contains(&patient.clinical_record_number) {
+            return Err(HospitalError::AdjacentBedNotFree);
+        }
+        Ok(())
+    }
+
+    /// `check_roommate` against the patient already in `occupant_bed`, naming them in the
+    /// error (`RoomIncompatible`)
+    fn check_occupant(
+        &self,
+        patient: &Patient,
+        occupant_bed: u32,
+        occupant: &Patient,
+    ) -> Result<(), HospitalError> {
+        self.check_roommate(patient, occupant, occupant_bed)
+            .map_err(|reason| HospitalError::RoomIncompatible {
+                bed: occupant_bed,
+                clinical_record: occupant.clinical_record_number,
+                reason: Box::new(reason),
+            })
+    }
+
+    /// Whether a patient in `bed_number` must have their room to themselves
+    fn needs_own_room(&self, patient: &Patient, bed_number: u32) -> bool {
+        !self.block_reasons_for(patient, bed_number).is_empty()
+    }
+
+    // ----------------- Waitlist -----------------
+
+    /// Queues a patient who couldn't be given a bed: behind everyone of the same or higher
+    /// acuity, ahead of anyone of lower acuity
+    pub fn add_to_waitlist(&mut self, patient: Patient) {
+        let position = self
+            .waitlist
+            .iter()
+            .position(|waiting| waiting.acuity < patient.acuity)
+            .unwrap_or(self.waitlist.len());
+        self.waitlist.insert(position, patient);
+    }
+
+    /// Patients currently waiting, in the order they'll be served
+    pub fn waitlist(&self) -> &VecDeque<Patient> {
+        &self.waitlist
+    }
+
+    /// Tries to admit waiting patients in order (highest acuity first, then FIFO), e.g. after
+    /// a discharge, each into their recommended bed. Returns the (CRN, bed) pairs placed; the
+    /// rest keep their place in line.
+    pub fn try_admit_from_waitlist(&mut self) -> Vec<(u32, u32)> {
+        let mut admitted = Vec::new();
+        let mut still_waiting = VecDeque::new();
+
+        while let Some(patient) = self.waitlist.pop_front() {
+            let clinical_record = patient.clinical_record_number;
+            match self.recommend_bed(&patient) {
+                Some(bed_number) if self.admit_patient(&patient, bed_number).is_ok() => {
+                    admitted.push((clinical_record, bed_number));
+                }
+                _ => still_waiting.push_back(patient),
+            }
+        }
+
+        self.waitlist = still_waiting;
+        admitted
+    }
+
+    // ----------------- Audit -----------------
+
+    /// Returns every recorded state change, oldest first
+    pub fn audit_log(&self) -> &[AuditEvent] {
+        &self.audit_log
+    }
+
+    /// Every recorded event concerning a patient, oldest first (a per-patient timeline)
+    pub fn patient_history(&self, clinical_record: u32) -> Vec<&AuditEvent> {
+        self.audit_log
+            .iter()
+            .filter(|event| event.involves(clinical_record))
+            .collect()
+    }
+
+    /// Clears the audit log (e.g. after it has been exported)
+    pub fn clear_audit_log(&mut self) {
+        self.audit_log.clear();
+    }
+
+    /// Registers an observer notified after every admission, discharge, move and (un)block
+    pub fn add_observer(&mut self, observer: Box<dyn HospitalObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Opens a channel that receives every event recorded from now on (each subscriber
+    /// gets its own). Dropping the receiver unsubscribes.
+    pub fn subscribe(&mut self) -> Receiver<HospitalEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// When an admitted patient was admitted (None if they aren't admitted)
+    pub fn admitted_at(&self, clinical_record: u32) -> Option<SystemTime> {
+        self.admission_times.get(&clinical_record).copied()
+    }
+
+    /// Sets the expected stay used by `next_likely_free_bed`
+    pub fn set_average_length_of_stay(&mut self, stay: Duration) {
+        self.average_length_of_stay = stay;
+    }
+
+    /// Estimates which bed of `unit` frees up soonest, and how long until then: a vacant bed
+    /// right away, a bed being cleaned when its cleaning ends, an occupied bed once its
+    /// patient has stayed the average length of stay (zero if they're overdue). Blocked and
+    /// reserved beds and beds in open-ended maintenance aren't considered. Ties go to the lowest bed number.
+    /// This is a heuristic for admission planning, not a promise.
+    pub fn next_likely_free_bed(&self, unit: u16) -> Option<(u32, Duration)> {
+        let now = self.now();
+        let remaining = |until: SystemTime| until.duration_since(now).unwrap_or_default();
+
+        self.beds_in_unit(unit)
+            .into_iter()
+            .filter_map(|n| {
+                let wait = match &self.beds[&n].state {
+                    BedState::Vacant => Duration::ZERO,
+                    BedState::Maintenance => remaining(*self.cleaning_until.get(&n)?),
+                    BedState::Occupied(p) => {
+                        let admitted = self.admitted_at(p.clinical_record_number)?;
+                        remaining(admitted + self.average_length_of_stay)
+                    }
+                    BedState::Blocked { .. } | BedState::Reserved { .. } => return None,
+                };
+                Some((n, wait))
+            })
+            .min_by_key(|&(n, wait)| (wait, n))
+    }
+
+    /// Current time used to timestamp audit events
+    fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    /// Replaces the clock (e.g. with a `ManualClock` in tests)
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Runs an operation that only changes beds as a whole: its events are held back until
+    /// it succeeds, and if it fails the beds are put back as they were and its events
+    /// dropped, as if it had never started
+    fn atomically<T>(
+        &mut self,
+        operation: impl FnOnce(&mut Self) -> Result<T, HospitalError>,
+    ) -> Result<T, HospitalError> {
+        let beds = self.beds.clone();
+        let outer = self.held_events.replace(Vec::new());
+        let result = operation(self);
+        let held = std::mem::replace(&mut self.held_events, outer).unwrap_or_default();
+        match result {
+            // Published now (or handed to the enclosing operation, if there is one)
+            Ok(_) => held.into_iter().for_each(|event| self.record(event)),
+            Err(_) => self.beds = beds,
+        }
+        result
+    }
 
-    /// Finds a patient by CRN and returns (bed number, patient)
-    pub fn find_patient_info(&self, clinical_record: u32) -> Option<(u16, Patient)> {
-        for (bed_number, bed) in &self.beds {
-            if let BedState::Occupied(patient) = &bed.state {
-                if patient.clinical_record_number == clinical_record {
-                    return Some((*bed_number, patient.clone()));
+    /// Logs an event and forwards it to the observers. Only called once the change is
+    /// committed (events of an operation in progress wait in `held_events`).
+    fn record(&mut self, event: AuditEvent) {
+        if let Some(held) = &mut self.held_events {
+            held.push(event);
+            return;
+        }
+        for observer in &mut self.observers {
+            match event {
+                AuditEvent::Admitted {
+                    clinical_record,
+                    bed,
+                    ..
+                } => observer.on_admit(clinical_record, bed),
+                AuditEvent::Discharged {
+                    clinical_record,
+                    bed,
+                    ..
+                } => observer.on_discharge(clinical_record, bed),
+                AuditEvent::Moved {
+                    clinical_record,
+                    from,
+                    to,
+                    ..
+                } => observer.on_move(clinical_record, from, to),
+                AuditEvent::Switched {
+                    clinical_record1,
+                    bed1,
+                    clinical_record2,
+                    bed2,
+                    ..
+                } => {
+                    observer.on_move(clinical_record1, bed2, bed1);
+                    observer.on_move(clinical_record2, bed1, bed2);
                 }
+                AuditEvent::Blocked {
+                    bed,
+                    clinical_record,
+                    ..
+                } => observer.on_block(bed, clinical_record),
+                AuditEvent::Unblocked {
+                    bed,
+                    clinical_record,
+                    ..
+                } => observer.on_unblock(bed, clinical_record),
+                AuditEvent::MarkedInfected { .. }
+                | AuditEvent::SetVip { .. }
+                | AuditEvent::Maintenance { .. }
+                | AuditEvent::ManualBlock { .. }
+                | AuditEvent::Reserved { .. } => {}
             }
         }
-        None
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        self.audit_log.push(event);
+    }
+
+    fn record_blocked(&mut self, bed: u32, clinical_record: u32) {
+        self.record(AuditEvent::Blocked {
+            bed,
+            clinical_record,
+            at: self.now(),
+        });
+    }
+
+    fn record_unblocked(&mut self, bed: u32, clinical_record: u32) {
+        self.record(AuditEvent::Unblocked {
+            bed,
+            clinical_record,
+            at: self.now(),
+        });
+    }
+
+    fn record_set_vip(&mut self, clinical_record: u32, bed: u32, is_vip: bool) {
+        self.record(AuditEvent::SetVip {
+            clinical_record,
+            bed,
+            is_vip,
+            at: self.now(),
+        });
+    }
+
+    // ----------------- Queries -----------------
+
+    /// Finds a patient by CRN and returns (bed number, patient).
+    /// Beds are scanned in order, so the result is deterministic even if a bug ever admitted
+    /// the same CRN twice (`validate_invariants` reports that as `DuplicateClinicalRecord`).
+    pub fn find_patient_info(&self, clinical_record: u32) -> Option<(u32, Patient)> {
+        self.iter_beds()
+            .find_map(|(bed_number, bed)| match &bed.state {
+                BedState::Occupied(p) if p.clinical_record_number == clinical_record => {
+                    Some((bed_number, p.clone()))
+                }
+                _ => None,
+            })
+    }
+
+    /// Admitted patients carrying `tag` (exact match), sorted by bed number
+    pub fn find_patients_with_tag(&self, tag: &str) -> Vec<(u32, &Patient)> {
+        self.iter_beds()
+            .filter_map(|(bed_number, bed)| match &bed.state {
+                BedState::Occupied(p) if p.tags.iter().any(|t| t == tag) => Some((bed_number, p)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Finds patients whose name contains `query` (case-insensitive), sorted by bed number.
+    /// An empty query matches nobody.
+    pub fn find_patients_by_name(&self, query: &str) -> Vec<(u32, &Patient)> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let query = query.to_lowercase();
+
+        let mut found: Vec<(u32, &Patient)> = self
+            .beds
+            .iter()
+            .filter_map(|(&bed_number, bed)| match &bed.state {
+                BedState::Occupied(p) if p.name.to_lowercase().contains(&query) => {
+                    Some((bed_number, p))
+                }
+                _ => None,
+            })
+            .collect();
+        found.sort_unstable_by_key(|&(bed_number, _)| bed_number);
+        found
+    }
+
+    /// Every infectious patient admitted, with their bed and the bed blocked on their behalf,
+    /// sorted by bed number (for infection control)
+    pub fn infection_report(&self) -> Vec<InfectionEntry> {
+        self.iter_beds()
+            .filter_map(|(bed_number, bed)| match &bed.state {
+                BedState::Occupied(p) if p.is_infected => Some(InfectionEntry {
+                    clinical_record: p.clinical_record_number,
+                    name: p.name.clone(),
+                    bed: bed_number,
+                    blocked_bed: self.other_room_members(bed_number).into_iter().find(|n| {
+                        self.beds[n]
+                            .block_reasons()
+                            .contains(&BlockReason::AdjacentInfected(p.clinical_record_number))
+                    }),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Beds blocked next to an infectious patient, sorted: environmental services clean
+    /// these first
+    pub fn high_priority_clean_beds(&self) -> Vec<u32> {
+        self.beds_matching(|bed, _| {
+            bed.block_reasons()
+                .iter()
+                .any(|r| matches!(r, BlockReason::AdjacentInfected(_)))
+        })
+    }
+
+    /// Every room with at least one patient, ordered by unit then room (for rounds)
+    pub fn patients_by_room(&self) -> Vec<RoomOccupancy> {
+        self.units()
+            .into_iter()
+            .flat_map(|unit| self.rooms_in_unit(unit))
+            .filter(|room| !room.is_empty())
+            .map(|room| {
+                let occupants = room.occupants();
+                let beds = room
+                    .bed_numbers()
+                    .into_iter()
+                    .map(|n| {
+                        let patient = occupants.iter().find(|&&(m, _)| m == n);
+                        (n, patient.map(|&(_, p)| p.clone()))
+                    })
+                    .collect();
+                RoomOccupancy { beds }
+            })
+            .collect()
+    }
+
+    /// Returns the beds currently blocked because of a VIP/infectious patient (sorted).
+    /// Empty if the patient isn't admitted or doesn't require blocking.
+    pub fn beds_blocked_by(&self, clinical_record: u32) -> Vec<u32> {
+        match self.find_patient_info(clinical_record) {
+            Some((bed_number, _)) => self
+                .other_room_members(bed_number)
+                .into_iter()
+                .filter(|n| {
+                    self.beds[n]
+                        .block_reasons()
+                        .iter()
+                        .any(|r| r.clinical_record() == Some(clinical_record))
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Beds that would become vacant if the patient were discharged now: their own bed plus
+    /// the beds blocked only because of them (sorted). Empty if the patient isn't admitted.
+    pub fn beds_freed_by_discharge(&self, clinical_record: u32) -> Vec<u32> {
+        let Some((bed_number, _)) = self.find_patient_info(clinical_record) else {
+            return Vec::new();
+        };
+        let mut freed: Vec<u32> = self
+            .other_room_members(bed_number)
+            .into_iter()
+            .filter(|n| {
+                let reasons = self.beds[n].block_reasons();
+                !reasons.is_empty()
+                    && reasons
+                        .iter()
+                        .all(|r| r.clinical_record() == Some(clinical_record))
+            })
+            .collect();
+        freed.push(bed_number);
+        freed.sort_unstable();
+        freed
+    }
+
+    /// Copies the current state of every bed (compare two of them with `diff_snapshots`)
+    pub fn snapshot(&self) -> HospitalSnapshot {
+        HospitalSnapshot {
+            beds: self
+                .beds
+                .iter()
+                .map(|(&bed_number, bed)| (bed_number, bed.state.clone()))
+                .collect(),
+        }
     }
 
-    /// Counts beds by state (occupied, vacant, blocked)
-    pub fn count_beds_by_state(&self) -> (usize, usize, usize) {
+    /// Counts beds by state (occupied, vacant, blocked, maintenance). Reserved beds count as
+    /// blocked: nobody else can take them.
+    pub fn count_beds_by_state(&self) -> (usize, usize, usize, usize) {
         let mut occupied = 0;
         let mut vacant = 0;
         let mut blocked = 0;
+        let mut maintenance = 0;
 
         for bed in self.beds.values() {
             match bed.state {
                 BedState::Occupied(_) => occupied += 1,
                 BedState::Vacant => vacant += 1,
-                BedState::Blocked => blocked += 1,
+                BedState::Blocked { .. } | BedState::Reserved { .. } => blocked += 1,
+                BedState::Maintenance => maintenance += 1,
             }
         }
 
-        (occupied, vacant, blocked)
+        (occupied, vacant, blocked, maintenance)
     }
 
-    /// Prints to console the state of all beds (useful for manual demo)
-    pub fn list_all_beds(&self) {
-        for &unit in &VALID_UNITS {
-            println!("\n--- Unit {} ---", unit);
-            for idx in FIRST_BED_INDEX..=LAST_BED_INDEX {
-                let bed_number = unit * 100 + idx;
-                if let Some(bed) = self.beds.get(&bed_number) {
-                    match &bed.state {
-                        BedState::Occupied(p) => {
-                            print!(
-                                "Bed {}: OCCUPIED - {} ({})",
-                                bed_number, p.name, p.clinical_record_number
-                            );
-                            if p.is_infected {
-                                print!(" [INFECTIOUS]");
-                            }
-                            if p.is_vip {
-                                print!(" [VIP]");
-                            }
-                            println!();
-                        }
-                        BedState::Vacant => println!("Bed {}: VACANT", bed_number),
-                        BedState::Blocked => println!("Bed {}: BLOCKED", bed_number),
+    /// Occupancy counts for the whole hospital and per unit, plus infectious/VIP patients
+    pub fn summary(&self) -> OccupancySummary {
+        let units: Vec<UnitOccupancy> = self
+            .unit_index
+            .iter()
+            .map(|(&unit, bed_numbers)| {
+                let mut counts = UnitOccupancy {
+                    unit,
+                    total: bed_numbers.len(),
+                    occupied: 0,
+                    vacant: 0,
+                    blocked: 0,
+                    maintenance: 0,
+                    reserved: 0,
+                };
+                for n in bed_numbers {
+                    match self.beds[n].state {
+                        BedState::Occupied(_) => counts.occupied += 1,
+                        BedState::Vacant => counts.vacant += 1,
+                        BedState::Blocked { .. } => counts.blocked += 1,
+                        BedState::Maintenance => counts.maintenance += 1,
+                        BedState::Reserved { .. } => counts.reserved += 1,
                     }
                 }
-            }
+                counts
+            })
+            .collect();
+
+        OccupancySummary {
+            total: units.iter().map(|u| u.total).sum(),
+            occupied: units.iter().map(|u| u.occupied).sum(),
+            vacant: units.iter().map(|u| u.vacant).sum(),
+            blocked: units.iter().map(|u| u.blocked).sum(),
+            maintenance: units.iter().map(|u| u.maintenance).sum(),
+            reserved: units.iter().map(|u| u.reserved).sum(),
+            units,
+            infected: self.count_infected(),
+            vip: self.count_vip(),
         }
     }
 
-    /// Returns all available beds for a specific patient
-    pub fn get_available_beds_for_patient(&self, patient: &Patient) -> Vec<u16> {
-        let mut available = Vec::new();
+    /// Units whose occupancy rate (`UnitOccupancy::occupancy_rate`) is above `pct`, a
+    /// fraction (0.8 = 80%), sorted
+    pub fn units_over_threshold(&self, pct: f64) -> Vec<u16> {
+        self.summary()
+            .units
+            .iter()
+            .filter(|u| u.occupancy_rate() > pct)
+            .map(|u| u.unit)
+            .collect()
+    }
 
-        for (&bed_number, bed) in &self.beds {
-            if !bed.is_available() {
-                continue;
-            }
+    /// Counts admitted patients for which `predicate` holds
+    pub fn count_patients_where(&self, predicate: impl Fn(&Patient) -> bool) -> usize {
+        self.beds
+            .values()
+            .filter(|bed| matches!(&bed.state, BedState::Occupied(p) if predicate(p)))
+            .count()
+    }
 
-            // Children under 13 -> only unit 5
-            let unit = bed_number / 100;
-            if patient.age < 13 && unit != 5 {
-                continue;
+    /// Number of infectious patients admitted
+    pub fn count_infected(&self) -> usize {
+        self.count_patients_where(|p| p.is_infected)
+    }
+
+    /// Number of VIP patients admitted
+    pub fn count_vip(&self) -> usize {
+        self.count_patients_where(|p| p.is_vip)
+    }
+
+    /// Number of minors admitted (per the current admission rules)
+    pub fn count_minors(&self) -> usize {
+        self.count_patients_where(|p| self.rules.is_minor(p))
+    }
+
+    /// Occupied beds per unit, split by gender: unit → (male, female, other).
+    /// Every unit appears, even with no patients.
+    pub fn gender_breakdown(&self) -> HashMap<u16, (usize, usize, usize)> {
+        let mut breakdown: HashMap<u16, (usize, usize, usize)> = self
+            .unit_index
+            .keys()
+            .map(|&unit| (unit, (0, 0, 0)))
+            .collect();
+
+        for (&bed_number, bed) in self.beds.iter() {
+            if let BedState::Occupied(p) = &bed.state {
+                let counts = breakdown.entry(self.unit_of(bed_number)).or_default();
+                match p.gender {
+                    Gender::Male => counts.0 += 1,
+                    Gender::Female => counts.1 += 1,
+                    Gender::Other => counts.2 += 1,
+                }
             }
+        }
 
-            let roommate_number = Self::roommate_of(bed_number);
-            let mut can_admit = true;
+        breakdown
+    }
 
-            if let Some(roommate_bed) = self.beds.get(&roommate_number) {
-                if let BedState::Occupied(roommate) = &roommate_bed.state {
-                    if patient.gender != roommate.gender {
-                        can_admit = false;
-                    }
-                    if (patient.age < 16) != (roommate.age < 16) {
-                        can_admit = false;
-                    }
-                    if roommate.is_infected || roommate.is_vip {
-                        can_admit = false;
-                    }
+    /// Number of beds in use or usable: every bed except blocked and maintenance ones
+    pub fn capacity(&self) -> usize {
+        self.beds
+            .values()
+            .filter(|bed| matches!(bed.state, BedState::Occupied(_) | BedState::Vacant))
+            .count()
+    }
+
+    /// How many more regular (not VIP/infectious) patients of this gender and age could be
+    /// admitted right now, one after another: a room with one man can take more men but no
+    /// women, and an empty 2-bed room takes two patients of the same profile.
+    pub fn remaining_capacity_for(&self, gender: Gender, age: u8) -> usize {
+        let mut seen_rooms = HashSet::new();
+        let mut remaining = 0;
+        for (&unit, bed_numbers) in &self.unit_index {
+            let mut unit_remaining = 0;
+            for &bed_number in bed_numbers {
+                let members = self.room_members(bed_number);
+                if !seen_rooms.insert(members[0])
+                    || self.check_unit_for(gender, age, &[], bed_number).is_err()
+                {
+                    continue;
                 }
-                if (patient.is_infected || patient.is_vip) && !roommate_bed.is_available() {
-                    can_admit = false;
+                let compatible = self
+                    .room_occupants(bed_number)
+                    .iter()
+                    .all(|&(n, p)| self.profile_fits_roommate(gender, age, p, n));
+                if !compatible {
+                    continue;
                 }
-            }
 
-            if can_admit {
-                available.push(bed_number);
+                let free = members
+                    .iter()
+                    .filter(|n| self.beds[n].is_available())
+                    .count();
+                if self.is_unit_isolated(unit) {
+                    // Isolation unit: one patient per fully free room
+                    unit_remaining += usize::from(free == members.len());
+                } else {
+                    unit_remaining += free;
+                }
             }
+            // A gender cap limits the unit as a whole
+            remaining += self
+                .gender_cap_left(unit, gender, &[])
+                .map_or(unit_remaining, |left| unit_remaining.min(left));
         }
+        remaining
+    }
 
-        available.sort_unstable();
-        available
+    /// Number of beds lost to adjacent blocking (VIP/infectious patients take a whole room).
+    /// Beds blocked only by hand (`BlockReason::Other`) aren't lost to any patient.
+    pub fn effective_capacity_lost(&self) -> usize {
+        self.beds
+            .values()
+            .filter(|bed| {
+                bed.block_reasons().iter().any(|reason| {
+                    matches!(
+                        reason,
+                        BlockReason::AdjacentVip(_)
+                            | BlockReason::AdjacentInfected(_)
+                            | BlockReason::AdjacentIsolation(_)
+                            | BlockReason::PrivateRoom(_)
+                    )
+                })
+            })
+            .count()
     }
-}
 
-impl Default for Hospital {
-    fn default() -> Self {
+    /// Number of vacant beds that could actually take some patient right now: a vacant bed
+    /// next to someone who needs their room to themselves doesn't count
+    pub fn available_capacity(&self) -> usize {
+        self.beds
+            .iter()
+            .filter(|(&bed_number, bed)| {
+                bed.is_available()
+                    && self.other_room_members(bed_number).iter().all(|n| {
+                        match &self.beds[n].state {
+                            BedState::Occupied(p) => !self.needs_own_room(p, *n),
+                            _ => true,
+                        }
+                    })
+            })
+            .count()
+    }
+
+    /// Frees beds left blocked on behalf of a patient who no longer requires it (e.g. after
+    /// a bug or a direct state change). Only patient blocks are dropped: manual ones
+    /// (`BlockReason::Other`) stay. Returns the beds that became vacant, sorted.
+    pub fn reclaim_orphaned_blocks(&mut self) -> Vec<u32> {
+        let mut orphans: Vec<(u32, BlockReason)> = self
+            .beds
+            .iter()
+            .flat_map(|(&bed_number, bed)| {
+                bed.block_reasons()
+                    .iter()
+                    .map(move |reason| (bed_number, *reason))
+            })
+            .filter(|(bed_number, reason)| {
+                reason.clinical_record().is_some() && !self.is_block_justified(*bed_number, reason)
+            })
+            .collect();
+        orphans.sort_unstable_by_key(|&(bed_number, _)| bed_number);
+
+        let mut reclaimed = Vec::new();
+        for (bed_number, reason) in orphans {
+            let bed = self.beds.get_mut(&bed_number).unwrap();
+            if bed.state.remove_block_reasons(|r| *r == reason) {
+                reclaimed.push(bed_number);
+                if let Some(clinical_record) = reason.clinical_record() {
+                    self.record_unblocked(bed_number, clinical_record);
+                }
+            }
+        }
+        self.debug_assert_invariants();
+        reclaimed
+    }
+
+    /// Is a patient block on `bed_number` still required by someone in its room?
+    fn is_block_justified(&self, bed_number: u32, reason: &BlockReason) -> bool {
+        self.other_room_members(bed_number).iter().any(|n| {
+            matches!(&self.beds[n].state, BedState::Occupied(p)
+                if self.block_reasons_for(p, *n).contains(reason))
+        })
+    }
+
+    /// Panics in debug builds if an operation left the hospital in a state the rules forbid
+    /// (compiled out in release builds)
+    fn debug_assert_invariants(&self) {
+        debug_assert!(
+            self.validate_invariants().is_empty(),
+            "invariants broken: {:?}",
+            self.validate_invariants()
+        );
+    }
+
+    /// Checks the whole hospital for states the rules forbid (a QA/debugging tool): blocks
+    /// without a VIP/infectious patient in the room, incompatible roommates, children
+    /// outside the pediatric unit and patients in two beds. Sorted by bed number; empty when
+    /// everything is consistent.
+    pub fn validate_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+        let mut seen: HashMap<u32, u32> = HashMap::new(); // CRN → first bed
+        let mut bed_numbers: Vec<u32> = self.beds.keys().copied().collect();
+        bed_numbers.sort_unstable();
+
+        for bed_number in bed_numbers {
+            match &self.beds[&bed_number].state {
+                BedState::Blocked { reasons } => {
+                    for reason in reasons {
+                        let Some(clinical_record) = reason.clinical_record() else {
+                            continue; // Not tied to a patient
+                        };
+                        if !self.is_block_justified(bed_number, reason) {
+                            violations.push(InvariantViolation::UnjustifiedBlock {
+                                bed: bed_number,
+                                clinical_record,
+                            });
+                        }
+                    }
+                }
+                BedState::Occupied(patient) => {
+                    if let Some(first_bed) = seen.insert(patient.clinical_record_number, bed_number)
+                    {
+                        violations.push(InvariantViolation::DuplicateClinicalRecord {
+                            bed: bed_number,
+                            first_bed,
+                            clinical_record: patient.clinical_record_number,
+                        });
+                    }
+                    if !self.rules.allows_unit(patient, self.unit_of(bed_number)) {
+                        violations.push(InvariantViolation::ChildOutsidePediatricUnit {
+                            bed: bed_number,
+                            clinical_record: patient.clinical_record_number,
+                        });
+                    }
+                    for roommate_number in self.other_room_members(bed_number) {
+                        let BedState::Occupied(roommate) = &self.beds[&roommate_number].state
+                        else {
+                            continue;
+                        };
+                        // Each pair is checked once, from its lower bed
+                        if roommate_number > bed_number {
+                            if patient.gender != roommate.gender && !self.is_family_room(bed_number)
+                            {
+                                violations.push(InvariantViolation::GenderMismatch {
+                                    bed: bed_number,
+                                    roommate: roommate_number,
+                                });
+                            }
+                            if self.rules.is_minor(patient) != self.rules.is_minor(roommate) {
+                                violations.push(InvariantViolation::AgeGroupMismatch {
+                                    bed: bed_number,
+                                    roommate: roommate_number,
+                                });
+                            }
+                        }
+                        // Unit isolation doesn't count: patients already sharing a room when
+                        // it started are left where they are
+                        let needs_own_room = self.rules.needs_own_room(patient)
+                            || self.private_rooms.contains(&patient.clinical_record_number);
+                        if needs_own_room {
+                            violations.push(InvariantViolation::SharedIsolationRoom {
+                                bed: bed_number,
+                                roommate: roommate_number,
+                            });
+                        }
+                    }
+                }
+                BedState::Vacant | BedState::Maintenance | BedState::Reserved { .. } => {}
+            }
+        }
+
+        violations
+    }
+
+    /// Occupancy as a render-ready grid: one row per unit (sorted), each with its beds in
+    /// order and their state kind
+    pub fn grid(&self) -> Vec<(u16, Vec<(u32, BedStateKind)>)> {
+        self.unit_index
+            .iter()
+            .map(|(&unit, bed_numbers)| {
+                let cells = bed_numbers
+                    .iter()
+                    .map(|&n| (n, self.beds[&n].state.kind()))
+                    .collect();
+                (unit, cells)
+            })
+            .collect()
+    }
+
+    /// Prints to console the state of all beds (useful for manual demo)
+    pub fn list_all_beds(&self) {
+        let mut current_unit = None;
+        for bed_number in self.sorted_bed_numbers() {
+            let unit = self.unit_of(bed_number);
+            if current_unit != Some(unit) {
+                println!("\n--- Unit {} ---", unit);
+                current_unit = Some(unit);
+            }
+            println!("Bed {}: {}", bed_number, self.beds[&bed_number].state);
+        }
+    }
+
+    /// Writes the occupancy of every bed as CSV, one row per bed sorted by bed number.
+    /// Columns: `bed_number,unit,state,crn,name,age,gender,infected,vip`; the patient columns
+    /// are left empty for beds without a patient.
+    pub fn export_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "bed_number,unit,state,crn,name,age,gender,infected,vip"
+        )?;
+
+        let mut bed_numbers: Vec<u32> = self.beds.keys().copied().collect();
+        bed_numbers.sort_unstable();
+
+        for bed_number in bed_numbers {
+            let unit = self.unit_of(bed_number);
+            match &self.beds[&bed_number].state {
+                BedState::Occupied(p) => {
+                    writeln!(
+                        writer,
+                        "{},{},OCCUPIED,{},{},{},{},{},{}",
+                        bed_number,
+                        unit,
+                        p.clinical_record_number,
+                        csv_field(&p.name),
+                        p.age,
+                        p.gender.code(),
+                        p.is_infected,
+                        p.is_vip
+                    )?;
+                }
+                state => {
+                    let label = match state {
+                        BedState::Blocked { .. } => "BLOCKED",
+                        BedState::Maintenance => "MAINTENANCE",
+                        BedState::Reserved { .. } => "RESERVED",
+                        _ => "VACANT",
+                    };
+                    writeln!(writer, "{},{},{},,,,,,", bed_number, unit, label)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns every vacant bed, sorted, regardless of who could use it
+    pub fn all_vacant_beds(&self) -> Vec<u32> {
+        self.beds_matching(|bed, _| bed.is_available())
+    }
+
+    /// Returns the beds for which `predicate(bed, bed_number)` holds, sorted
+    pub fn beds_matching(&self, predicate: impl Fn(&Bed, u32) -> bool) -> Vec<u32> {
+        self.iter_beds()
+            .filter(|&(bed_number, bed)| predicate(bed, bed_number))
+            .map(|(bed_number, _)| bed_number)
+            .collect()
+    }
+
+    /// Returns all available beds for a specific patient, sorted (single rooms first for
+    /// patients who require isolation). Beds reserved for this patient and reservations that
+    /// have already expired count as available.
+    pub fn get_available_beds_for_patient(&self, patient: &Patient) -> Vec<u32> {
+        let mut available = Vec::new();
+
+        // Children can only be in the pediatric unit: don't even look at the others
+        let units: Vec<&Vec<u32>> = if self.rules.is_pediatric(patient) {
+            self.unit_index.get(&PEDIATRIC_UNIT).into_iter().collect()
+        } else {
+            self.unit_index.values().collect()
+        };
+
+        for &bed_number in units.into_iter().flatten() {
+            let bed = &self.beds[&bed_number];
+            if !self.is_free_for(bed, patient.clinical_record_number) {
+                continue;
+            }
+
+            if self.fits_in_bed(patient, bed_number, &self.other_room_members(bed_number)) {
+                available.push(bed_number);
+            }
+        }
+
+        self.sort_available(patient, &mut available);
+        available
+    }
+
+    /// Like `get_available_beds_for_patient` for several patients at once (CRN → beds):
+    /// every vacant bed and its room are looked at once, whatever the number of patients
+    pub fn available_beds_for_all(&self, patients: &[Patient]) -> HashMap<u32, Vec<u32>> {
+        let mut available: HashMap<u32, Vec<u32>> = patients
+            .iter()
+            .map(|p| (p.clinical_record_number, Vec::new()))
+            .collect();
+
+        for &bed_number in self.unit_index.values().flatten() {
+            let bed = &self.beds[&bed_number];
+            if !matches!(bed.state, BedState::Vacant | BedState::Reserved { .. }) {
+                continue;
+            }
+            let roommates = self.other_room_members(bed_number);
+            for patient in patients {
+                if self.is_free_for(bed, patient.clinical_record_number)
+                    && self.fits_in_bed(patient, bed_number, &roommates)
+                {
+                    available
+                        .get_mut(&patient.clinical_record_number)
+                        .unwrap()
+                        .push(bed_number);
+                }
+            }
+        }
+
+        for patient in patients {
+            let beds = available.get_mut(&patient.clinical_record_number).unwrap();
+            self.sort_available(patient, beds);
+        }
+        available
+    }
+
+    /// Can the patient take this vacant bed, given the other beds of its room?
+    fn fits_in_bed(&self, patient: &Patient, bed_number: u32, roommates: &[u32]) -> bool {
+        // Children -> only unit 5; gendered units -> only their gender
+        if self.check_unit(patient, bed_number).is_err()
+            || !self.beds[&bed_number].meets_requirements(patient)
+        {
+            return false;
+        }
+
+        roommates.iter().all(|roommate_number| {
+            let roommate_bed = &self.beds[roommate_number];
+            if let BedState::Occupied(roommate) = &roommate_bed.state {
+                if self.check_roommate(patient, roommate, bed_number).is_err() {
+                    return false;
+                }
+            }
+            !self.needs_own_room(patient, bed_number) || roommate_bed.is_available()
+        })
+    }
+
+    /// Sorts a patient's available beds (single rooms first for patients who require isolation)
+    fn sort_available(&self, patient: &Patient, available: &mut [u32]) {
+        available.sort_unstable();
+        if patient.requires_isolation {
+            // Single rooms first: nobody has to be blocked for them
+            available.sort_by_key(|&n| !self.other_room_members(n).is_empty());
+        }
+    }
+
+    /// The unit with the most beds this patient could take right now, to spread the load
+    /// (ties go to the lowest unit). None if no bed fits them.
+    pub fn least_occupied_unit(&self, patient: &Patient) -> Option<u16> {
+        let mut per_unit: BTreeMap<u16, usize> = BTreeMap::new();
+        for bed_number in self.get_available_beds_for_patient(patient) {
+            *per_unit.entry(self.unit_of(bed_number)).or_default() += 1;
+        }
+        per_unit
+            .into_iter()
+            .fold(
+                None,
+                |best: Option<(u16, usize)>, (unit, count)| match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((unit, count)),
+                },
+            )
+            .map(|(unit, _)| unit)
+    }
+
+    /// Recommends the best bed for a patient among the valid ones (None if there is none).
+    ///
+    /// Scoring (higher wins, ties go to the lowest bed number):
+    /// - +2 if the room already has a (compatible) occupant: consolidates patients and keeps
+    ///   empty rooms free for VIP/infectious patients, who need a whole room to themselves.
+    /// - +1 if the bed is in the unit the patient belongs to: unit 5 for children, any other
+    ///   unit for everyone else (so pediatric beds stay free for children).
+    /// - +4 if the patient requires isolation and the bed is a single room (no bed blocked).
+    pub fn recommend_bed(&self, patient: &Patient) -> Option<u32> {
+        self.recommend_bed_where(patient, |_| true)
+    }
+
+    /// `recommend_bed`, only considering beds for which `accept(bed_number)` holds
+    fn recommend_bed_where(&self, patient: &Patient, accept: impl Fn(u32) -> bool) -> Option<u32> {
+        let mut best: Option<(u32, u8)> = None;
+
+        for bed_number in self.get_available_beds_for_patient(patient) {
+            if !accept(bed_number) {
+                continue;
+            }
+            let mut score = 0;
+            let room_has_occupant = self
+                .other_room_members(bed_number)
+                .iter()
+                .any(|n| matches!(self.beds[n].state, BedState::Occupied(_)));
+            if room_has_occupant {
+                score += 2;
+            }
+            if (self.unit_of(bed_number) == PEDIATRIC_UNIT) == self.rules.is_pediatric(patient) {
+                score += 1;
+            }
+            if patient.requires_isolation && self.other_room_members(bed_number).is_empty() {
+                score += 4;
+            }
+
+            if best.is_none_or(|(best_bed, best_score)| {
+                score > best_score || (score == best_score && bed_number < best_bed)
+            }) {
+                best = Some((bed_number, score));
+            }
+        }
+
+        best.map(|(bed_number, _)| bed_number)
+    }
+
+    /// Proposes moves (patient -> target bed) that pair up patients who are alone in shared
+    /// rooms of `unit`, freeing whole rooms. Nothing is moved: every move is valid against the
+    /// current state, and no room is involved in more than one move, so they can be applied
+    /// in any order.
+    pub fn suggest_consolidation(&self, unit: u16) -> Vec<(u32, u32)> {
+        // Patients alone in a room that has space for someone else
+        let alone: Vec<(u32, &Patient)> = self
+            .rooms_in_unit(unit)
+            .into_iter()
+            .filter(|room| room.size() > 1)
+            .filter_map(|room| match room.occupants()[..] {
+                [only] => Some(only),
+                _ => None,
+            })
+            .collect();
+
+        let mut used_rooms: HashSet<u32> = HashSet::new();
+        let mut moves = Vec::new();
+        for (i, &(bed_number, patient)) in alone.iter().enumerate() {
+            let room = self.room_members(bed_number)[0];
+            if used_rooms.contains(&room) {
+                continue;
+            }
+            let target = alone[i + 1..].iter().find_map(|&(other_bed, _)| {
+                let other_room = self.room_members(other_bed)[0];
+                if used_rooms.contains(&other_room) {
+                    return None;
+                }
+                self.free_bed_in_room(other_bed)
+                    .filter(|&free| self.can_admit(patient, free).is_ok())
+                    .map(|free| (other_room, free))
+            });
+            if let Some((other_room, free)) = target {
+                used_rooms.insert(room);
+                used_rooms.insert(other_room);
+                moves.push((patient.clinical_record_number, free));
+            }
+        }
+        moves
+    }
+}
+
+/// Moves a patient from `source` to bed `bed_number` of `dest`. All or nothing: the
+/// destination is checked before the patient leaves the source, so if it rejects them
+/// neither hospital changes.
+pub fn transfer(
+    source: &mut Hospital,
+    dest: &mut Hospital,
+    clinical_record: u32,
+    bed_number: u32,
+) -> Result<(), HospitalError> {
+    let (_, patient) = source
+        .find_patient_info(clinical_record)
+        .ok_or(HospitalError::PatientNotFound(clinical_record))?;
+
+    // The source can't always take the patient back (e.g. their unit was isolated or made
+    // single-gender since they were admitted), so don't let them go until dest accepts them
+    if let Some((existing_bed, _)) = dest.find_patient_info(clinical_record) {
+        return Err(HospitalError::PatientAlreadyAdmitted(existing_bed));
+    }
+    dest.can_admit(&patient, bed_number)?;
+
+    let patient = source.transfer_out(clinical_record)?;
+    dest.admit_patient(&patient, bed_number)
+        .expect("destination accepted the patient in can_admit");
+    Ok(())
+}
+
+/// Checks that a layout has beds, unique units whose bed numbers fit in a u32, and rooms of a
+/// usable size
+pub(crate) fn validate_layout(
+    units: &[u16],
+    first_index: u16,
+    last_index: u16,
+    beds_per_room: u16,
+) -> Result<(), LayoutError> {
+    if first_index > last_index {
+        return Err(LayoutError::EmptyBedRange {
+            first: first_index,
+            last: last_index,
+        });
+    }
+    if beds_per_room == 0 || beds_per_room > last_index - first_index + 1 {
+        return Err(LayoutError::InvalidRoomSize(beds_per_room));
+    }
+
+    let base = bed_number_base(last_index);
+    let mut seen_units = HashSet::new();
+    for &unit in units {
+        if !seen_units.insert(unit) {
+            return Err(LayoutError::DuplicateUnit(unit));
+        }
+        // The largest bed number of the unit is the one that could overflow
+        bed_number(unit, last_index, base)?;
+    }
+
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Default for Hospital {
+    fn default() -> Self {
         Self::new()
     }
 }
@@ -513,6 +2914,7 @@ impl Default for Hospital {
 mod tests {
     use super::*;
     use crate::domain::constants::TOTAL_BEDS; // Import ONLY in tests
+    use crate::domain::patient::Gender;
 
     #[test]
     fn test_hospital_creates_all_beds_with_constants() {
@@ -524,4 +2926,2699 @@ mod tests {
         assert!(h.beds.contains_key(&505));
         assert!(h.beds.contains_key(&506));
     }
+
+    #[test]
+    fn test_audit_log_records_admit_then_discharge() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "Maria".into(), 30, Gender::Female, false, false);
+
+        h.admit_patient(&p, 101).unwrap();
+        h.discharge_patient(10001).unwrap();
+
+        let log = h.audit_log();
+        assert_eq!(log.len(), 2);
+        assert!(matches!(
+            log[0],
+            AuditEvent::Admitted {
+                clinical_record: 10001,
+                bed: 101,
+                ..
+            }
+        ));
+        assert!(matches!(
+            log[1],
+            AuditEvent::Discharged {
+                clinical_record: 10001,
+                bed: 101,
+                ..
+            }
+        ));
+
+        h.clear_audit_log();
+        assert!(h.audit_log().is_empty());
+    }
+
+    #[test]
+    fn test_switch_vip_with_normal_moves_the_block() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let normal = Patient::new(10002, "Normal".into(), 40, Gender::Male, false, false);
+
+        h.admit_patient(&vip, 101).unwrap(); // blocks 102
+        h.admit_patient(&normal, 201).unwrap();
+        assert!(h.beds[&102].is_blocked());
+
+        h.switch_patients(10001, 10002).unwrap();
+
+        // The VIP's block followed them to 202, and 102 is usable again
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 201);
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 101);
+        assert!(h.beds[&202].is_blocked());
+        assert!(h.beds[&102].is_available());
+    }
+
+    #[test]
+    fn test_discharge_bed_returns_patient_and_unblocks() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+
+        h.admit_patient(&vip, 101).unwrap();
+        assert!(h.beds[&102].is_blocked());
+
+        assert_eq!(h.discharge_bed(101), Ok(vip));
+        assert!(h.beds[&101].is_available());
+        assert!(h.beds[&102].is_available());
+
+        // Vacant, blocked or missing beds can't be discharged
+        assert_eq!(
+            h.discharge_bed(101),
+            Err(HospitalError::BedNotOccupied(101))
+        );
+        assert_eq!(h.discharge_bed(301), Err(HospitalError::BedNotFound(301)));
+    }
+
+    #[test]
+    fn test_discharge_patient_returns_changed_beds() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let p = Patient::new(10002, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&vip, 101).unwrap();
+        h.admit_patient(&p, 201).unwrap();
+
+        assert_eq!(
+            h.discharge_patient(10001),
+            Ok(DischargeOutcome {
+                freed_bed: 101,
+                unblocked_bed: Some(102),
+            })
+        );
+        assert_eq!(
+            h.discharge_patient(10002),
+            Ok(DischargeOutcome {
+                freed_bed: 201,
+                unblocked_bed: None,
+            })
+        );
+        assert_eq!(
+            h.discharge_patient(10001),
+            Err(HospitalError::PatientNotFound(10001))
+        );
+    }
+
+    #[test]
+    fn test_mark_infected_returns_roommate_new_bed() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 30, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 35, Gender::Male, false, false);
+        let p3 = Patient::new(10003, "P3".into(), 40, Gender::Male, false, false);
+
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 102).unwrap();
+        h.admit_patient(&p3, 201).unwrap();
+
+        let new_bed = h.mark_patient_as_infected(10001).unwrap();
+        assert_eq!(new_bed, h.find_patient_info(10002).map(|(bed, _)| bed));
+        assert!(new_bed.is_some());
+
+        // No roommate to relocate
+        assert_eq!(h.set_patient_vip(10003, true), Ok(None));
+    }
+
+    #[test]
+    fn test_relocated_roommate_stays_in_same_unit() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 30, Gender::Female, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 35, Gender::Female, false, false);
+
+        h.admit_patient(&p1, 201).unwrap();
+        h.admit_patient(&p2, 202).unwrap();
+
+        // Unit 1 has lower-numbered free beds, but unit 2 still has room
+        let new_bed = h.set_patient_vip(10001, true).unwrap();
+        assert_eq!(new_bed, Some(203));
+        assert_eq!(h.find_patient_info(10002).unwrap().0 / 100, 2);
+    }
+
+    #[test]
+    fn test_recommend_bed_consolidates_compatible_patients() {
+        let mut h = Hospital::new();
+        let m1 = Patient::new(10001, "M1".into(), 30, Gender::Male, false, false);
+        let m2 = Patient::new(10002, "M2".into(), 40, Gender::Male, false, false);
+
+        let first = h.recommend_bed(&m1).unwrap();
+        assert_eq!(first, 101);
+        h.admit_patient(&m1, first).unwrap();
+
+        // Second male joins the half-full room instead of opening a new one
+        assert_eq!(h.recommend_bed(&m2), Some(102));
+
+        // Children always go to the pediatric unit; a female skips the male room
+        let child = Patient::new(10003, "Child".into(), 8, Gender::Female, false, false);
+        assert_eq!(h.recommend_bed(&child), Some(501));
+        let f = Patient::new(10004, "F".into(), 30, Gender::Female, false, false);
+        assert_eq!(h.recommend_bed(&f), Some(103));
+    }
+
+    #[test]
+    fn test_configurable_pediatric_threshold() {
+        let mut h = Hospital::new();
+        let teen = Patient::new(10001, "Teen".into(), 16, Gender::Female, false, false);
+
+        // Default rules: a 16-year-old is an adult for unit purposes
+        assert!(h.admission_rules().allows_unit(&teen, 1));
+
+        h.set_admission_rules(AdmissionRules {
+            pediatric_max_age: 18,
+            ..AdmissionRules::default()
+        })
+        .unwrap();
+        assert_eq!(
+            h.admit_patient(&teen, 101),
+            Err(HospitalError::PediatricUnitRequired)
+        );
+        assert_eq!(h.recommend_bed(&teen), Some(501));
+        assert!(h.admit_patient(&teen, 501).is_ok());
+    }
+
+    #[test]
+    fn test_can_admit_matches_admit_patient_without_mutating() {
+        let mut h = Hospital::new();
+        let male = Patient::new(10001, "M".into(), 30, Gender::Male, false, false);
+        let vip = Patient::new(10002, "VIP".into(), 60, Gender::Male, false, true);
+        h.admit_patient(&male, 201).unwrap();
+        h.admit_patient(&vip, 101).unwrap(); // Blocks 102
+
+        let female = Patient::new(10003, "F".into(), 30, Gender::Female, false, false);
+        let child = Patient::new(10004, "Child".into(), 9, Gender::Female, false, false);
+        let cases = [
+            (
+                &female,
+                202,
+                HospitalError::RoomIncompatible {
+                    bed: 201,
+                    clinical_record: 10001,
+                    reason: Box::new(HospitalError::GenderMismatch),
+                },
+            ),
+            (&female, 102, HospitalError::BedNotAvailable(102)),
+            (&female, 301, HospitalError::BedNotFound(301)),
+            (&child, 203, HospitalError::PediatricUnitRequired),
+        ];
+
+        for (patient, bed, expected) in cases {
+            let before = h.count_beds_by_state();
+            assert_eq!(h.can_admit(patient, bed), Err(expected.clone()));
+            assert_eq!(h.count_beds_by_state(), before);
+            assert_eq!(h.admit_patient(patient, bed), Err(expected));
+        }
+
+        assert_eq!(h.can_admit(&female, 203), Ok(()));
+        assert!(h.beds[&203].is_available());
+    }
+
+    #[test]
+    fn test_beds_blocked_by_patient() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 45, Gender::Female, false, false);
+        h.admit_patient(&p, 101).unwrap();
+        assert!(h.beds_blocked_by(10001).is_empty());
+
+        h.set_patient_vip(10001, true).unwrap();
+        assert_eq!(h.beds_blocked_by(10001), vec![102]);
+
+        h.discharge_patient(10001).unwrap();
+        assert!(h.beds_blocked_by(10001).is_empty());
+    }
+
+    #[test]
+    fn test_shared_block_stays_until_last_reason_removed() {
+        let mut h = Hospital::with_beds_per_room(3);
+        h.admit_patient(&Patient::adult_male(10001, "P1", 50), 101)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "P2", 55), 103)
+            .unwrap();
+
+        // Roommates already sharing the bay stay together when the unit goes into
+        // isolation; bed 102 coming back from maintenance is blocked on behalf of both
+        h.set_unit_isolation(1, true);
+        h.set_maintenance(102, true).unwrap();
+        h.set_maintenance(102, false).unwrap();
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[
+                BlockReason::AdjacentInfected(10001),
+                BlockReason::AdjacentInfected(10002)
+            ]
+        );
+
+        h.discharge_patient(10001).unwrap();
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[BlockReason::AdjacentInfected(10002)]
+        );
+        assert_eq!(h.beds_blocked_by(10002), vec![102]);
+
+        h.discharge_patient(10002).unwrap();
+        assert!(h.beds[&102].is_available());
+    }
+
+    #[test]
+    fn test_vip_and_infected_blocks_are_released_independently() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Female, false, true);
+        h.admit_patient(&vip, 101).unwrap();
+
+        h.mark_patient_as_infected(10001).unwrap();
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[
+                BlockReason::AdjacentVip(10001),
+                BlockReason::AdjacentInfected(10001)
+            ]
+        );
+
+        h.unmark_patient_as_infected(10001).unwrap();
+        assert!(h.beds[&102].is_blocked());
+
+        h.set_patient_vip(10001, false).unwrap();
+        assert!(h.beds[&102].is_available());
+    }
+
+    #[test]
+    fn test_maintenance_beds_cannot_be_used() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 30, Gender::Male, false, false);
+
+        h.set_maintenance(103, true).unwrap();
+        assert!(h.beds[&103].is_in_maintenance());
+        assert_eq!(h.count_beds_by_state(), (0, 151, 0, 1));
+        assert!(!h.get_available_beds_for_patient(&p).contains(&103));
+        assert_eq!(
+            h.admit_patient(&p, 103),
+            Err(HospitalError::BedNotAvailable(103))
+        );
+
+        h.set_maintenance(103, false).unwrap();
+        assert!(h.admit_patient(&p, 103).is_ok());
+    }
+
+    #[test]
+    fn test_maintenance_on_occupied_bed_errors() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 30, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+
+        assert_eq!(
+            h.set_maintenance(101, true),
+            Err(HospitalError::BedOccupied(101))
+        );
+        assert!(!h.beds[&101].is_in_maintenance());
+    }
+
+    #[test]
+    fn test_waitlisted_child_admitted_after_discharge() {
+        let mut h = Hospital::new();
+        // Fill the pediatric unit
+        for i in 0..38 {
+            let child = Patient::new(20000 + i, "Child".into(), 8, Gender::Male, false, false);
+            h.admit_patient(&child, 501 + i).unwrap();
+        }
+
+        let waiting = Patient::new(10001, "Waiting".into(), 9, Gender::Male, false, false);
+        assert_eq!(h.recommend_bed(&waiting), None);
+        h.add_to_waitlist(waiting);
+        assert!(h.try_admit_from_waitlist().is_empty());
+        assert_eq!(h.waitlist().len(), 1);
+
+        h.discharge_patient(20005).unwrap(); // Frees 506
+        assert_eq!(h.try_admit_from_waitlist(), vec![(10001, 506)]);
+        assert!(h.waitlist().is_empty());
+    }
+
+    #[test]
+    fn test_find_patients_by_name() {
+        let mut h = Hospital::new();
+        let names = [
+            (10001, "Maria GARCIA", 201),
+            (10002, "José García", 101),
+            (10003, "Pedro garcia-Lopez", 103),
+            (10004, "Ana Martinez", 105),
+        ];
+        for (crn, name, bed) in names {
+            let p = Patient::new(crn, name.into(), 40, Gender::Female, false, false);
+            h.admit_patient(&p, bed).unwrap();
+        }
+
+        let found: Vec<(u32, u32)> = h
+            .find_patients_by_name("Garcia")
+            .into_iter()
+            .map(|(bed, p)| (bed, p.clinical_record_number))
+            .collect();
+        assert_eq!(found, vec![(103, 10003), (201, 10001)]); // "García" has an accent
+
+        assert_eq!(h.find_patients_by_name("tine").len(), 1);
+        assert!(h.find_patients_by_name("").is_empty());
+        assert!(h.find_patients_by_name("Smith").is_empty());
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let mut h = Hospital::new();
+        let p = Patient::new(
+            10001,
+            "Garcia, Maria".into(),
+            30,
+            Gender::Female,
+            false,
+            true,
+        );
+        h.admit_patient(&p, 101).unwrap(); // VIP blocks 102
+
+        let mut out = Vec::new();
+        h.export_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 1 + TOTAL_BEDS);
+        assert_eq!(
+            rows[0],
+            "bed_number,unit,state,crn,name,age,gender,infected,vip"
+        );
+        assert_eq!(
+            rows[1],
+            "101,1,OCCUPIED,10001,\"Garcia, Maria\",30,F,false,true"
+        );
+        assert_eq!(rows[2], "102,1,BLOCKED,,,,,,");
+        assert_eq!(rows[3], "103,1,VACANT,,,,,,");
+        assert!(rows[1..].iter().all(|r| r.split(',').count() >= 9));
+    }
+
+    #[test]
+    fn test_single_rooms_have_no_roommate() {
+        let mut h = Hospital::new().with_single_rooms(&[101, 102]);
+        assert_eq!(h.roommate_of_bed(101), None);
+        assert_eq!(h.roommate_of_bed(102), None);
+        assert_eq!(h.roommate_of_bed(103), Some(104));
+        assert_eq!(h.roommate_of_bed(999), None);
+
+        let male = Patient::new(10001, "M".into(), 30, Gender::Male, false, false);
+        let female = Patient::new(10002, "F".into(), 30, Gender::Female, false, false);
+        let vip = Patient::new(10003, "VIP".into(), 30, Gender::Female, false, true);
+        h.admit_patient(&male, 101).unwrap();
+        h.admit_patient(&female, 102).unwrap();
+
+        // A VIP in a single room has nothing to block
+        h.discharge_patient(10002).unwrap();
+        h.admit_patient(&vip, 102).unwrap();
+        assert_eq!(h.count_beds_by_state(), (2, TOTAL_BEDS - 2, 0, 0));
+    }
+
+    #[test]
+    fn test_observer_sees_admit_and_discharge() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[derive(Default)]
+        struct Counter {
+            admits: Arc<AtomicUsize>,
+            discharges: Arc<AtomicUsize>,
+        }
+        impl HospitalObserver for Counter {
+            fn on_admit(&mut self, _: u32, _: u32) {
+                self.admits.fetch_add(1, Ordering::SeqCst);
+            }
+            fn on_discharge(&mut self, _: u32, _: u32) {
+                self.discharges.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let counter = Counter::default();
+        let (admits, discharges) = (counter.admits.clone(), counter.discharges.clone());
+        let mut h = Hospital::new();
+        h.add_observer(Box::new(counter));
+
+        let p = Patient::new(10001, "P".into(), 30, Gender::Male, false, false);
+        let child = Patient::new(10002, "Child".into(), 5, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+        assert!(h.admit_patient(&child, 103).is_err()); // Failures aren't reported
+        h.move_patient(10001, 201).unwrap();
+        h.discharge_patient(10001).unwrap();
+
+        assert_eq!(admits.load(Ordering::SeqCst), 1);
+        assert_eq!(discharges.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_room_occupants() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 30, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        assert_eq!(h.room_occupants(102), vec![(101, &p1)]);
+
+        h.admit_patient(&p2, 102).unwrap();
+        assert_eq!(h.room_occupants(101), vec![(101, &p1), (102, &p2)]);
+        assert_eq!(h.room_occupants(102), vec![(101, &p1), (102, &p2)]);
+        assert!(h.room_occupants(103).is_empty());
+        assert!(h.room_occupants(999).is_empty());
+    }
+
+    #[test]
+    fn test_layout_validation() {
+        assert!(Hospital::try_new().is_ok());
+        assert_eq!(validate_layout(&VALID_UNITS, 1, 38, 2), Ok(()));
+
+        // Units of 150 beds are numbered 1001..=1150, clear of unit 2
+        assert_eq!(validate_layout(&[1, 2], 1, 150, 2), Ok(()));
+        assert_eq!(
+            validate_layout(&[1], 10, 5, 2),
+            Err(LayoutError::EmptyBedRange { first: 10, last: 5 })
+        );
+        assert_eq!(
+            validate_layout(&[1, 1], 1, 38, 2),
+            Err(LayoutError::DuplicateUnit(1))
+        );
+        assert_eq!(
+            validate_layout(&[u16::MAX], 1, 10000, 2),
+            Err(LayoutError::BedNumberOverflow {
+                unit: u16::MAX,
+                index: 10000
+            })
+        );
+        assert_eq!(
+            validate_layout(&[1], 1, 38, 0),
+            Err(LayoutError::InvalidRoomSize(0))
+        );
+        assert!(Hospital::try_with_beds_per_room(0).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_diff_after_admission() {
+        use crate::domain::snapshot::diff_snapshots;
+
+        let mut h = Hospital::new();
+        let before = h.snapshot();
+        let p = Patient::new(10001, "P".into(), 30, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+        let after = h.snapshot();
+
+        let changes = diff_snapshots(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].bed, 101);
+        assert_eq!(changes[0].before, Some(BedState::Vacant));
+        assert_eq!(changes[0].after, Some(BedState::Occupied(p)));
+        assert!(diff_snapshots(&after, &after.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_isolation_unit_blocks_neighbors() {
+        let mut h = Hospital::new();
+        h.set_unit_isolation(2, true);
+        assert!(h.is_unit_isolated(2));
+
+        let p1 = Patient::new(10001, "P1".into(), 30, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p1, 201).unwrap();
+        assert_eq!(h.beds_blocked_by(10001), vec![202]);
+        assert!(!h.get_available_beds_for_patient(&p2).contains(&202));
+        assert_eq!(
+            h.admit_patient(&p2, 202),
+            Err(HospitalError::BedNotAvailable(202))
+        );
+
+        // Other units are unaffected
+        h.admit_patient(&p2, 101).unwrap();
+        assert!(h.beds_blocked_by(10002).is_empty());
+
+        h.discharge_patient(10001).unwrap();
+        h.set_unit_isolation(2, false);
+        h.admit_patient(&p1, 201).unwrap();
+        assert!(h.beds_blocked_by(10001).is_empty());
+    }
+
+    #[test]
+    fn test_capacity_lost_to_vip_blocks() {
+        let mut h = Hospital::new();
+        assert_eq!(h.effective_capacity_lost(), 0);
+        assert_eq!(h.available_capacity(), TOTAL_BEDS);
+
+        let vip1 = Patient::new(10001, "VIP1".into(), 50, Gender::Male, false, true);
+        let vip2 = Patient::new(10002, "VIP2".into(), 60, Gender::Female, false, true);
+        h.admit_patient(&vip1, 101).unwrap();
+        h.admit_patient(&vip2, 201).unwrap();
+
+        assert_eq!(h.effective_capacity_lost(), 2);
+        assert_eq!(h.available_capacity(), TOTAL_BEDS - 4);
+
+        // Manual blocks aren't lost to a patient, even on a bed a VIP also blocks
+        h.block_bed(405, BlockReason::Other).unwrap();
+        h.block_bed(102, BlockReason::Other).unwrap();
+        assert_eq!(h.effective_capacity_lost(), 2);
+        h.unblock_bed(405, BlockReason::Other).unwrap();
+        h.unblock_bed(102, BlockReason::Other).unwrap();
+
+        // A unit put in isolation after the fact: the roommate's bed is vacant but unusable
+        let p = Patient::new(10003, "P".into(), 30, Gender::Male, false, false);
+        h.admit_patient(&p, 401).unwrap();
+        h.set_unit_isolation(4, true);
+        assert_eq!(h.available_capacity(), TOTAL_BEDS - 6);
+    }
+
+    #[test]
+    fn test_vip_blocking_can_be_turned_off() {
+        let mut h = Hospital::new();
+        h.set_admission_rules(AdmissionRules {
+            vip_blocks_adjacent: false,
+            ..AdmissionRules::default()
+        })
+        .unwrap();
+
+        let p1 = Patient::new(10001, "P1".into(), 30, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 102).unwrap();
+
+        assert_eq!(h.set_patient_vip(10001, true), Ok(None));
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 102);
+        assert!(h.beds_blocked_by(10001).is_empty());
+        assert!(h.find_patient_info(10001).unwrap().1.is_vip);
+
+        // Infection still blocks
+        h.discharge_patient(10002).unwrap();
+        h.mark_patient_as_infected(10001).unwrap();
+        assert_eq!(h.beds_blocked_by(10001), vec![102]);
+    }
+
+    #[test]
+    fn test_move_patient_to_best() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 50, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 203).unwrap();
+
+        // Joins the patient in 203 (consolidation); the old bed is freed
+        assert_eq!(h.move_patient_to_best(10001), Ok(204));
+        assert!(h.beds[&101].is_available());
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 204);
+
+        // Nowhere to go: with the pediatric unit full, the child stays where it is
+        let mut h = Hospital::new();
+        for i in 0..38 {
+            let child = Patient::new(20000 + i, "Child".into(), 8, Gender::Male, false, false);
+            h.admit_patient(&child, 501 + i).unwrap();
+        }
+        assert_eq!(
+            h.move_patient_to_best(20000),
+            Err(HospitalError::NoCompatibleBed)
+        );
+        assert_eq!(h.find_patient_info(20000).unwrap().0, 501);
+    }
+
+    #[test]
+    fn test_validate_invariants_reports_corruption() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let male = Patient::new(10002, "M".into(), 40, Gender::Male, false, false);
+        let female = Patient::new(10003, "F".into(), 40, Gender::Female, false, false);
+        let child = Patient::new(10004, "Child".into(), 6, Gender::Female, false, false);
+        h.admit_patient(&vip, 101).unwrap();
+        h.admit_patient(&male, 201).unwrap();
+        assert!(h.validate_invariants().is_empty());
+
+        // Corrupt the state behind the hospital's back
+        h.beds.get_mut(&104).unwrap().state = BedState::Blocked {
+            reasons: vec![BlockReason::AdjacentInfected(10002)],
+        };
+        h.beds.get_mut(&202).unwrap().state = BedState::Occupied(female);
+        h.beds.get_mut(&401).unwrap().state = BedState::Occupied(child);
+
+        assert_eq!(
+            h.validate_invariants(),
+            vec![
+                InvariantViolation::UnjustifiedBlock {
+                    bed: 104,
+                    clinical_record: 10002
+                },
+                InvariantViolation::GenderMismatch {
+                    bed: 201,
+                    roommate: 202
+                },
+                InvariantViolation::ChildOutsidePediatricUnit {
+                    bed: 401,
+                    clinical_record: 10004
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vacant_and_matching_beds() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let child = Patient::new(10002, "Child".into(), 7, Gender::Male, false, false);
+        h.admit_patient(&vip, 101).unwrap();
+        h.admit_patient(&child, 501).unwrap();
+
+        let vacant = h.all_vacant_beds();
+        assert_eq!(vacant.len(), TOTAL_BEDS - 3);
+        assert_eq!(&vacant[..2], &[103, 104]);
+
+        let unit5 = h.beds_matching(|bed, n| n / 100 == 5 && bed.is_available());
+        assert_eq!(unit5.len(), 37);
+        assert_eq!(unit5[0], 502);
+        assert!(unit5.iter().all(|n| (502..=538).contains(n)));
+    }
+
+    #[test]
+    fn test_discharge_unit_empties_it() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&vip, 401).unwrap(); // Blocks 402
+        for i in 0..10 {
+            let p = Patient::new(10002 + i, "P".into(), 40, Gender::Female, false, false);
+            h.admit_patient(&p, 403 + i).unwrap();
+        }
+        let elsewhere = Patient::new(10020, "Stays".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&elsewhere, 101).unwrap();
+
+        let discharged = h.discharge_unit(4);
+        assert_eq!(discharged.len(), 11);
+        assert_eq!(discharged[0], 10001);
+        assert!((401..=438).all(|n| h.beds[&n].is_available()));
+        assert_eq!(h.find_patient_info(10020).unwrap().0, 101);
+
+        assert!(h.discharge_unit(4).is_empty());
+    }
+
+    #[test]
+    fn test_count_patients_where() {
+        let mut h = Hospital::new();
+        let patients = [
+            (
+                Patient::new(10001, "A".into(), 40, Gender::Male, true, false),
+                101,
+            ),
+            (
+                Patient::new(10002, "B".into(), 50, Gender::Male, false, true),
+                103,
+            ),
+            (
+                Patient::new(10003, "C".into(), 60, Gender::Female, true, true),
+                105,
+            ),
+            (
+                Patient::new(10004, "D".into(), 8, Gender::Male, false, false),
+                501,
+            ),
+            (
+                Patient::new(10005, "E".into(), 15, Gender::Female, false, false),
+                201,
+            ),
+            (
+                Patient::new(10006, "F".into(), 30, Gender::Female, false, false),
+                203,
+            ),
+        ];
+        for (p, bed) in &patients {
+            h.admit_patient(p, *bed).unwrap();
+        }
+
+        assert_eq!(h.count_infected(), 2);
+        assert_eq!(h.count_vip(), 2);
+        assert_eq!(h.count_minors(), 2);
+        assert_eq!(h.count_patients_where(|p| p.gender == Gender::Female), 3);
+        assert_eq!(h.count_patients_where(|_| true), 6);
+    }
+
+    #[test]
+    fn test_gender_breakdown_per_unit() {
+        let mut h = Hospital::new();
+        let m1 = Patient::new(10001, "M1".into(), 40, Gender::Male, false, false);
+        let m2 = Patient::new(10002, "M2".into(), 50, Gender::Male, false, false);
+        let f1 = Patient::new(10003, "F1".into(), 30, Gender::Female, false, false);
+        let vip = Patient::new(10004, "X1".into(), 30, Gender::Other, false, true);
+        h.admit_patient(&m1, 101).unwrap();
+        h.admit_patient(&m2, 102).unwrap();
+        h.admit_patient(&f1, 201).unwrap();
+        h.admit_patient(&vip, 401).unwrap(); // The blocked bed isn't counted
+
+        let breakdown = h.gender_breakdown();
+        assert_eq!(breakdown[&1], (2, 0, 0));
+        assert_eq!(breakdown[&2], (0, 1, 0));
+        assert_eq!(breakdown[&4], (0, 0, 1));
+        assert_eq!(breakdown[&5], (0, 0, 0));
+
+        // The hospital's own units, whatever the layout
+        let h = Hospital::try_with_layout(&[7], 10, 2).unwrap();
+        let units: Vec<u16> = h.gender_breakdown().into_keys().collect();
+        assert_eq!(units, vec![7]);
+    }
+
+    #[test]
+    fn test_set_vip_rolls_back_when_roommate_cannot_move() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 50, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 102).unwrap();
+        // No other bed can take the roommate
+        for bed_number in h.all_vacant_beds() {
+            h.set_maintenance(bed_number, true).unwrap();
+        }
+
+        assert_eq!(
+            h.set_patient_vip(10001, true),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert!(!h.find_patient_info(10001).unwrap().1.is_vip);
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 102);
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_relocation_is_all_or_nothing_in_larger_rooms() {
+        let mut h = Hospital::with_beds_per_room(4);
+        for (crn, bed) in [(10001, 101), (10002, 102), (10003, 103)] {
+            let p = Patient::new(crn, "P".into(), 40, Gender::Male, false, false);
+            h.admit_patient(&p, bed).unwrap();
+        }
+        // A single free bed elsewhere: the first roommate fits, the second doesn't
+        for bed_number in h.all_vacant_beds() {
+            if bed_number != 201 && bed_number != 104 {
+                h.set_maintenance(bed_number, true).unwrap();
+            }
+        }
+
+        assert_eq!(
+            h.mark_patient_as_infected(10001),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 102);
+        assert_eq!(h.find_patient_info(10003).unwrap().0, 103);
+        assert!(!h.find_patient_info(10001).unwrap().1.is_infected);
+    }
+
+    #[test]
+    fn test_failed_relocation_leaves_no_trace_when_rules_changed() {
+        let mut h = Hospital::with_beds_per_room(4);
+        for (crn, bed) in [(10001, 101), (10002, 102), (10003, 103)] {
+            let p = Patient::new(crn, "P".into(), 40, Gender::Male, false, false);
+            h.admit_patient(&p, bed).unwrap();
+        }
+        for bed_number in h.all_vacant_beds() {
+            if bed_number != 201 {
+                h.set_maintenance(bed_number, true).unwrap();
+            }
+        }
+        // Unit 1 is over its cap now: the first roommate couldn't be moved back into it
+        h.set_gender_cap(1, Gender::Male, 1);
+        let events = h.subscribe();
+        let log_len = h.audit_log().len();
+
+        assert_eq!(
+            h.mark_patient_as_infected(10001),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert_eq!(
+            h.set_patient_vip(10001, true),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert_eq!(
+            h.assign_private_room(10001),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 102);
+        assert_eq!(h.find_patient_info(10003).unwrap().0, 103);
+        assert!(h.bed(201).unwrap().is_available());
+        assert_eq!(h.audit_log().len(), log_len);
+        assert_eq!(events.try_iter().count(), 0);
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_relocation_strategy_is_pluggable() {
+        use crate::domain::relocation::LowestBedStrategy;
+
+        let setup = || {
+            let mut h = Hospital::new();
+            let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+            let p2 = Patient::new(10002, "P2".into(), 50, Gender::Male, false, false);
+            h.admit_patient(&p1, 401).unwrap();
+            h.admit_patient(&p2, 402).unwrap();
+            h
+        };
+
+        let mut same_unit = setup();
+        assert_eq!(same_unit.set_patient_vip(10001, true), Ok(Some(403)));
+
+        let mut lowest = setup();
+        lowest.set_relocation_strategy(Box::new(LowestBedStrategy));
+        assert_eq!(lowest.set_patient_vip(10001, true), Ok(Some(101)));
+    }
+
+    #[test]
+    fn test_isolation_patient_prefers_single_room() {
+        // Bed 437 becomes a single room, which leaves 438 on its own too
+        let mut h = Hospital::new().with_single_rooms(&[437]);
+        let mut patient = Patient::new(10001, "Iso".into(), 40, Gender::Female, false, false);
+        patient.requires_isolation = true;
+
+        assert_eq!(h.get_available_beds_for_patient(&patient)[0], 437);
+        assert_eq!(h.recommend_bed(&patient), Some(437));
+        h.admit_patient(&patient, 437).unwrap();
+        assert!(h.beds_blocked_by(10001).is_empty());
+
+        let mut second = Patient::new(10002, "Iso2".into(), 40, Gender::Female, false, false);
+        second.requires_isolation = true;
+        assert_eq!(h.recommend_bed(&second), Some(438));
+        h.admit_patient(&second, 438).unwrap();
+
+        // With the single rooms taken, a shared room is used and its other bed blocked
+        let mut third = Patient::new(10003, "Iso3".into(), 40, Gender::Female, false, false);
+        third.requires_isolation = true;
+        assert_eq!(h.recommend_bed(&third), Some(101));
+        h.admit_patient(&third, 101).unwrap();
+        assert_eq!(h.beds_blocked_by(10003), vec![102]);
+    }
+
+    #[test]
+    fn test_failed_transfer_leaves_source_unchanged() {
+        let mut source = Hospital::new();
+        let mut dest = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        source.admit_patient(&vip, 101).unwrap();
+        let before = source.snapshot();
+
+        assert_eq!(
+            transfer(&mut source, &mut dest, 10001, 301),
+            Err(HospitalError::BedNotFound(301))
+        );
+        assert_eq!(source.snapshot(), before);
+        assert_eq!(dest.count_beds_by_state(), (0, TOTAL_BEDS, 0, 0));
+
+        assert_eq!(transfer(&mut source, &mut dest, 10001, 201), Ok(()));
+        assert_eq!(source.find_patient_info(10001), None);
+        assert_eq!(dest.find_patient_info(10001), Some((201, vip)));
+        assert_eq!(dest.beds_blocked_by(10001), vec![202]);
+    }
+
+    #[test]
+    fn test_failed_transfer_from_since_isolated_unit() {
+        let mut source = Hospital::new();
+        let mut dest = Hospital::new();
+        source
+            .admit_patient(&Patient::adult_male(10001, "P1", 40), 101)
+            .unwrap();
+        source
+            .admit_patient(&Patient::adult_male(10002, "P2", 50), 102)
+            .unwrap();
+        // Roommates stay together, but 10001 couldn't be re-admitted next to 10002 now
+        source.set_unit_isolation(1, true);
+        let before = source.snapshot();
+        let admitted_at = source.admitted_at(10001);
+
+        assert_eq!(
+            transfer(&mut source, &mut dest, 10001, 301),
+            Err(HospitalError::BedNotFound(301))
+        );
+        assert_eq!(source.snapshot(), before);
+        assert_eq!(source.admitted_at(10001), admitted_at);
+    }
+
+    #[test]
+    fn test_failed_transfer_from_since_single_gender_unit() {
+        let mut source = Hospital::new();
+        let mut dest = Hospital::new();
+        source
+            .admit_patient(&Patient::adult_male(10001, "P1", 40), 101)
+            .unwrap();
+        source.assign_private_room(10001).unwrap();
+        source.set_unit_gender(1, Some(Gender::Female));
+        let before = source.snapshot();
+        let log_len = source.audit_log().len();
+
+        dest.admit_patient(&Patient::adult_male(10002, "P2", 50), 201)
+            .unwrap();
+        dest.mark_patient_as_infected(10002).unwrap();
+        assert_eq!(
+            transfer(&mut source, &mut dest, 10001, 202),
+            Err(HospitalError::BedNotAvailable(202))
+        );
+        assert_eq!(source.snapshot(), before);
+        assert_eq!(source.audit_log().len(), log_len);
+        assert_eq!(source.beds_blocked_by(10001), vec![102]); // Still private
+    }
+
+    #[test]
+    fn test_duplicate_crn_is_detected() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+        assert!(h.validate_invariants().is_empty());
+
+        h.beds.get_mut(&203).unwrap().state = BedState::Occupied(p.clone()); // Corrupt
+        assert_eq!(
+            h.validate_invariants(),
+            vec![InvariantViolation::DuplicateClinicalRecord {
+                bed: 203,
+                first_bed: 101,
+                clinical_record: 10001
+            }]
+        );
+        assert_eq!(h.find_patient_info(10001), Some((101, p))); // First bed wins
+    }
+
+    #[test]
+    fn test_roommate_patient() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 50, Gender::Male, false, false);
+        let vip = Patient::new(10003, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&p1, 101).unwrap();
+        assert_eq!(h.roommate_patient(10001), None);
+
+        h.admit_patient(&p2, 102).unwrap();
+        h.admit_patient(&vip, 201).unwrap(); // Roommate bed is blocked
+        assert_eq!(h.roommate_patient(10001), Some(&p2));
+        assert_eq!(h.roommate_patient(10002), Some(&p1));
+        assert_eq!(h.roommate_patient(10003), None);
+        assert_eq!(h.roommate_patient(99999), None);
+    }
+
+    #[test]
+    fn test_block_reason_is_shown() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.set_patient_vip(10001, true).unwrap();
+
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[BlockReason::AdjacentVip(10001)]
+        );
+        assert_eq!(
+            h.beds[&102].state.to_string(),
+            "BLOCKED (next to VIP #10001)"
+        );
+
+        h.mark_patient_as_infected(10001).unwrap();
+        assert_eq!(
+            h.beds[&102].state.to_string(),
+            "BLOCKED (next to VIP #10001, next to infectious #10001)"
+        );
+    }
+
+    #[test]
+    fn test_admit_to_best_without_preference() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        let child = Patient::new(10002, "Child".into(), 6, Gender::Male, false, false);
+
+        assert_eq!(h.admit_to_best(&p, None), Ok(101));
+        // The pediatric rule wins over the preferred unit
+        assert_eq!(h.admit_to_best(&child, Some(2)), Ok(501));
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 501);
+    }
+
+    #[test]
+    fn test_admit_to_best_falls_back_when_preferred_unit_is_full() {
+        let mut h = Hospital::new();
+        for i in 0..38 {
+            let p = Patient::new(20000 + i, "F".into(), 40, Gender::Female, false, false);
+            h.admit_patient(&p, 401 + i).unwrap();
+        }
+
+        let p = Patient::new(10001, "P".into(), 40, Gender::Female, false, false);
+        assert_eq!(h.admit_to_best(&p, Some(4)), Ok(101));
+
+        let other = Patient::new(10002, "Q".into(), 40, Gender::Female, false, false);
+        assert_eq!(h.admit_to_best(&other, Some(2)), Ok(201));
+    }
+
+    #[test]
+    fn test_from_manifest() {
+        let h = Hospital::from_manifest(
+            r#"
+            # Unit 2
+            201: 10001 "Maria Garcia" 30 F vip
+            203: 10002 "John Lopez" 35 M
+            204: 10003 "Peter Sanchez" 40 M infected   # Can't share with John
+            "#,
+        );
+        assert_eq!(
+            h.err(),
+            Some(ManifestError {
+                line: 5,
+                reason: HospitalError::RoomIncompatible {
+                    bed: 203,
+                    clinical_record: 10002,
+                    reason: Box::new(HospitalError::AdjacentBedNotFree),
+                }
+                .to_string(),
+            })
+        );
+
+        let h = Hospital::from_manifest(
+            r#"
+            201: 10001 "Maria Garcia" 30 F vip
+            203: 10002 "John Lopez" 35 M
+            204: 10003 "Peter Sanchez" 40 M
+            501: 10004 "Carlos" 10 M
+            205: BLOCKED
+            206: MAINTENANCE
+            "#,
+        )
+        .unwrap();
+        assert_eq!(h.count_beds_by_state(), (4, TOTAL_BEDS - 7, 2, 1));
+        assert_eq!(h.beds[&205].block_reasons(), &[BlockReason::Other]);
+        assert!(h.find_patient_info(10001).unwrap().1.is_vip);
+
+        let bad = Hospital::from_manifest("101: 10001 \"Ana\" 30 Q");
+        assert_eq!(bad.err().unwrap().line, 1);
+        assert!(Hospital::from_manifest("\nabc").is_err());
+    }
+
+    #[test]
+    fn test_manifest_hash_in_quoted_name() {
+        let h = Hospital::from_manifest(
+            r##"
+            201: 10001 "Bed #3 Garcia" 30 F # A trailing comment
+            203: 10002 "#" 35 F
+            "##,
+        )
+        .unwrap();
+        assert_eq!(h.find_patient_info(10001).unwrap().1.name, "Bed #3 Garcia");
+        assert_eq!(h.find_patient_info(10002).unwrap().1.name, "#");
+    }
+
+    #[test]
+    fn test_manifest_round_trips_names_with_quotes() {
+        let mut h = Hospital::new();
+        let names = [
+            r#"Ana "La Flaca" Ruiz"#,
+            r##"Back\slash "#1""##,
+            "Two\nLines",
+        ];
+        for (i, name) in (0u32..).zip(names) {
+            let p = Patient::new(10001 + i, name.into(), 40, Gender::Female, false, false);
+            h.admit_patient(&p, 201 + 2 * i).unwrap();
+        }
+
+        let text = h.to_manifest();
+        assert!(text.contains(r#""Ana \"La Flaca\" Ruiz""#));
+        let loaded = Hospital::from_manifest(&text).unwrap();
+        for (i, name) in (0u32..).zip(names) {
+            assert_eq!(loaded.find_patient_info(10001 + i).unwrap().1.name, name);
+        }
+        assert_eq!(loaded.to_manifest(), text);
+    }
+
+    #[test]
+    fn test_reservations_survive_save_and_load() {
+        use crate::domain::clock::ManualClock;
+        use std::time::UNIX_EPOCH;
+
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000_000));
+        let hour = Duration::from_secs(60 * 60);
+        let mut h = Hospital::new();
+        h.set_clock(Box::new(clock.clone()));
+        h.admit_patient(&Patient::vip(10001, "Maria #1", 60, Gender::Female), 201)
+            .unwrap();
+        h.block_bed(202, BlockReason::Other).unwrap();
+        h.reserve_bed(101, 10002, Some(hour)).unwrap();
+        h.reserve_bed(103, 10003, None).unwrap();
+
+        assert_eq!(
+            h.to_manifest(),
+            "201: 10001 \"Maria #1\" 60 F vip\n\
+             101: RESERVED 10002 until 1003600\n\
+             103: RESERVED 10003\n\
+             202: BLOCKED\n"
+        );
+        let path = std::env::temp_dir().join(format!("bed_management_{}.txt", std::process::id()));
+        h.save_to_file(&path).unwrap();
+
+        let reloaded = Hospital::load_from_file(&path, Box::new(clock.clone())).unwrap();
+        assert_eq!(reloaded.snapshot(), h.snapshot());
+
+        clock.advance(2 * hour);
+        let reloaded = Hospital::load_from_file(&path, Box::new(clock.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded.bed_state(101), Some(&BedState::Vacant)); // Expired while saved
+        assert_eq!(
+            reloaded.bed_state(103),
+            Some(&BedState::Reserved {
+                clinical_record: 10003,
+                until: None
+            })
+        );
+        assert_eq!(
+            reloaded.bed(202).unwrap().block_reasons(),
+            &[BlockReason::AdjacentVip(10001), BlockReason::Other]
+        );
+        assert!(Hospital::from_manifest("101: RESERVED 10002 soon").is_err());
+    }
+
+    #[test]
+    fn test_units_and_their_beds() {
+        let h = Hospital::new();
+        assert_eq!(h.units(), VALID_UNITS.to_vec());
+        assert_eq!(h.beds_in_unit(5), (501..=538).collect::<Vec<u32>>());
+        assert!(h.beds_in_unit(3).is_empty());
+    }
+
+    #[test]
+    fn test_available_beds_scan_matches_can_admit() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let boy = Patient::new(10002, "Boy".into(), 9, Gender::Male, false, false);
+        h.admit_patient(&vip, 101).unwrap();
+        h.admit_patient(&boy, 503).unwrap();
+
+        let child = Patient::new(10003, "Girl".into(), 8, Gender::Female, false, false);
+        let adult = Patient::new(10004, "Adult".into(), 40, Gender::Male, false, false);
+        for patient in [&child, &adult] {
+            let expected = h.beds_matching(|_, n| h.can_admit(patient, n).is_ok());
+            assert_eq!(h.get_available_beds_for_patient(patient), expected);
+        }
+
+        let child_beds = h.get_available_beds_for_patient(&child);
+        assert_eq!(child_beds.len(), 36); // Not 503 nor its roommate 504
+        assert!(child_beds
+            .iter()
+            .all(|n| n / 100 == u32::from(PEDIATRIC_UNIT)));
+    }
+
+    #[test]
+    fn test_critical_patient_jumps_the_waitlist() {
+        use crate::domain::patient::Acuity;
+
+        let mut h = Hospital::new();
+        for i in 0..38 {
+            let child = Patient::new(20000 + i, "Child".into(), 8, Gender::Male, false, false);
+            h.admit_patient(&child, 501 + i).unwrap();
+        }
+
+        let mut low = Patient::new(10001, "Low".into(), 9, Gender::Male, false, false);
+        low.acuity = Acuity::Low;
+        let medium = Patient::new(10002, "Medium".into(), 9, Gender::Male, false, false);
+        let mut critical = Patient::new(10003, "Critical".into(), 9, Gender::Male, false, false);
+        critical.acuity = Acuity::Critical;
+        h.add_to_waitlist(low);
+        h.add_to_waitlist(medium);
+        h.add_to_waitlist(critical);
+
+        let order: Vec<u32> = h
+            .waitlist()
+            .iter()
+            .map(|p| p.clinical_record_number)
+            .collect();
+        assert_eq!(order, vec![10003, 10002, 10001]);
+
+        h.discharge_patient(20000).unwrap(); // One bed: 501
+        assert_eq!(h.try_admit_from_waitlist(), vec![(10003, 501)]);
+        assert_eq!(h.waitlist().len(), 2);
+    }
+
+    #[test]
+    fn test_free_bed_in_room() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        let vip = Patient::new(10002, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&p, 101).unwrap();
+        h.admit_patient(&vip, 201).unwrap(); // Blocks 202
+
+        assert_eq!(h.free_bed_in_room(101), Some(102));
+        assert_eq!(h.free_bed_in_room(102), None); // Roommate occupied
+        assert_eq!(h.free_bed_in_room(201), None); // Roommate blocked
+        assert_eq!(h.free_bed_in_room(301), None); // No such bed
+
+        let h = Hospital::new().with_single_rooms(&[437]);
+        assert_eq!(h.free_bed_in_room(437), None);
+    }
+
+    #[test]
+    fn test_suggest_consolidation() {
+        let mut h = Hospital::new();
+        let m1 = Patient::new(10001, "M1".into(), 40, Gender::Male, false, false);
+        let m2 = Patient::new(10002, "M2".into(), 50, Gender::Male, false, false);
+        let f = Patient::new(10003, "F".into(), 50, Gender::Female, false, false);
+        let vip = Patient::new(10004, "VIP".into(), 60, Gender::Male, false, true);
+        h.admit_patient(&m1, 101).unwrap();
+        h.admit_patient(&f, 103).unwrap(); // Can't pair with either man
+        h.admit_patient(&m2, 106).unwrap();
+        h.admit_patient(&vip, 107).unwrap(); // Needs the room to themselves
+
+        assert_eq!(h.suggest_consolidation(1), vec![(10001, 105)]);
+        assert!(h.suggest_consolidation(2).is_empty());
+
+        // Suggestions only: nobody moved
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+    }
+
+    #[test]
+    fn test_sorted_bed_numbers_groups_by_unit() {
+        let h = Hospital::new();
+        let numbers = h.sorted_bed_numbers();
+        assert_eq!(numbers.len(), TOTAL_BEDS);
+        assert_eq!(numbers, h.sorted_bed_numbers());
+
+        // Each unit's beds are contiguous, in index order
+        let units: Vec<u32> = numbers.iter().map(|n| n / 100).collect();
+        assert!(units.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(&numbers[..3], &[101, 102, 103]);
+        assert_eq!(numbers[37], 138);
+        assert_eq!(numbers[38], 201);
+        assert_eq!(numbers.last(), Some(&538));
+    }
+
+    #[test]
+    fn test_private_room() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 50, Gender::Male, false, false);
+        let other = Patient::new(10003, "Other".into(), 60, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 102).unwrap();
+
+        assert_eq!(h.assign_private_room(10001), Ok(101));
+        assert_ne!(h.find_patient_info(10002).unwrap().0, 102); // Roommate relocated
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[BlockReason::PrivateRoom(10001)]
+        );
+        assert!(!h.get_available_beds_for_patient(&other).contains(&102));
+        assert_eq!(
+            h.assign_private_room(99999),
+            Err(HospitalError::PatientNotFound(99999))
+        );
+
+        // The room follows the patient
+        h.move_patient(10001, 203).unwrap();
+        assert!(h.beds[&102].is_available());
+        assert!(h.beds[&204].is_blocked());
+
+        h.release_private_room(10001).unwrap();
+        assert!(h.beds[&204].is_available());
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_beds_freed_by_discharge() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let p = Patient::new(10002, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&vip, 102).unwrap();
+        h.admit_patient(&p, 201).unwrap();
+
+        assert_eq!(h.beds_freed_by_discharge(10001), vec![101, 102]);
+        assert_eq!(h.beds_freed_by_discharge(10002), vec![201]);
+        assert!(h.beds_freed_by_discharge(99999).is_empty());
+
+        // A bed still blocked for another reason wouldn't be freed
+        h.beds
+            .get_mut(&101)
+            .unwrap()
+            .state
+            .add_block_reasons(&[BlockReason::Other]);
+        assert_eq!(h.beds_freed_by_discharge(10001), vec![102]);
+        assert!(h.discharge_patient(10001).is_ok());
+        assert!(h.beds[&101].is_blocked());
+    }
+
+    #[test]
+    fn test_admission_warnings_for_child_next_to_infection() {
+        let mut h = Hospital::new();
+        let infected = Patient::new(10001, "Infected".into(), 40, Gender::Male, true, false);
+        let infected2 = Patient::new(10002, "Infected 2".into(), 50, Gender::Male, true, false);
+        let child = Patient::new(10003, "Child".into(), 6, Gender::Female, false, false);
+        let adult = Patient::new(10004, "Adult".into(), 40, Gender::Female, false, false);
+        h.admit_patient(&infected, 501).unwrap(); // Blocks 502
+        h.admit_patient(&infected2, 505).unwrap(); // Blocks 506
+
+        // Allowed by the rules, but next to the blocked 502
+        assert!(h.can_admit(&child, 503).is_ok());
+        assert_eq!(
+            h.admission_warnings(&child, 503),
+            vec![AdmissionWarning::AdjacentToIsolationBlock {
+                bed: 502,
+                clinical_record: 10001,
+            }]
+        );
+        assert_eq!(
+            h.admission_warnings(&child, 504),
+            vec![AdmissionWarning::AdjacentToInfectious {
+                bed: 505,
+                clinical_record: 10002,
+            }]
+        );
+        assert!(h.admission_warnings(&child, 509).is_empty());
+        assert!(h.admission_warnings(&adult, 503).is_empty());
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        let infected = Patient::new(10002, "Infected".into(), 40, Gender::Male, true, false);
+        let p = Patient::new(10003, "P".into(), 30, Gender::Female, false, false);
+        h.admit_patient(&vip, 101).unwrap(); // Blocks 102
+        h.admit_patient(&infected, 201).unwrap(); // Blocks 202
+        h.admit_patient(&p, 203).unwrap();
+
+        let summary = h.summary();
+        assert_eq!(summary.total, TOTAL_BEDS);
+        assert_eq!(summary.occupied, 3);
+        assert_eq!(summary.blocked, 2);
+        assert_eq!(summary.vacant, TOTAL_BEDS - 5);
+        assert_eq!((summary.infected, summary.vip), (1, 1));
+
+        assert_eq!(summary.units.len(), VALID_UNITS.len());
+        assert_eq!(
+            summary.units[0],
+            UnitOccupancy {
+                unit: 1,
+                total: 38,
+                occupied: 1,
+                vacant: 36,
+                blocked: 1,
+                maintenance: 0,
+                reserved: 0,
+            }
+        );
+        assert_eq!(summary.units[1].occupied, 2);
+    }
+
+    #[test]
+    fn test_switch_keeps_flags_and_admission_time_with_patient() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let mut h = Hospital::new();
+        let infected = Patient::new(10001, "Infected".into(), 40, Gender::Male, true, false);
+        let normal = Patient::new(10002, "Normal".into(), 50, Gender::Male, false, false);
+        h.admit_patient(&infected, 101).unwrap();
+        h.admit_patient(&normal, 203).unwrap();
+        assert!(h.admitted_at(10001).is_some());
+
+        // Make the two admission times distinguishable
+        let t1 = UNIX_EPOCH + Duration::from_secs(1_000);
+        let t2 = UNIX_EPOCH + Duration::from_secs(2_000);
+        h.admission_times.insert(10001, t1);
+        h.admission_times.insert(10002, t2);
+
+        h.switch_patients(10001, 10002).unwrap();
+
+        let (bed, p) = h.find_patient_info(10001).unwrap();
+        assert_eq!(bed, 203);
+        assert!(p.is_infected);
+        assert_eq!(h.admitted_at(10001), Some(t1));
+
+        let (bed, p) = h.find_patient_info(10002).unwrap();
+        assert_eq!(bed, 101);
+        assert!(!p.is_infected);
+        assert_eq!(h.admitted_at(10002), Some(t2));
+
+        h.discharge_patient(10001).unwrap();
+        assert_eq!(h.admitted_at(10001), None);
+    }
+
+    #[test]
+    fn test_total_beds_and_capacity() {
+        let mut h = Hospital::new();
+        assert_eq!(h.total_beds(), TOTAL_BEDS);
+        assert_eq!(h.capacity(), TOTAL_BEDS);
+
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&vip, 101).unwrap(); // Blocks 102
+        h.set_maintenance(201, true).unwrap();
+        assert_eq!(h.total_beds(), TOTAL_BEDS);
+        assert_eq!(h.capacity(), TOTAL_BEDS - 2);
+    }
+
+    #[test]
+    fn test_block_bed_by_hand() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+
+        assert_eq!(h.block_bed(102, BlockReason::Other), Ok(()));
+        assert_eq!(h.beds[&102].block_reasons(), &[BlockReason::Other]);
+        assert!(!h.get_available_beds_for_patient(&p).contains(&102));
+        assert_eq!(
+            h.block_bed(101, BlockReason::Other),
+            Err(HospitalError::BedOccupied(101))
+        );
+        assert_eq!(
+            h.block_bed(301, BlockReason::Other),
+            Err(HospitalError::BedNotFound(301))
+        );
+
+        // A discharge in the room doesn't release it
+        h.discharge_patient(10001).unwrap();
+        assert!(h.beds[&102].is_blocked());
+
+        assert_eq!(h.unblock_bed(102, BlockReason::Other), Ok(()));
+        assert!(h.beds[&102].is_available());
+    }
+
+    #[test]
+    fn test_patient_blocks_cannot_be_set_or_lifted_by_hand() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap(); // Blocks 102
+
+        for reason in [
+            BlockReason::AdjacentVip(10001),
+            BlockReason::AdjacentInfected(10001),
+            BlockReason::AdjacentIsolation(10001),
+            BlockReason::PrivateRoom(10001),
+        ] {
+            assert_eq!(
+                h.block_bed(105, reason),
+                Err(HospitalError::NotAManualBlock(reason))
+            );
+            assert_eq!(
+                h.unblock_bed(102, reason),
+                Err(HospitalError::NotAManualBlock(reason))
+            );
+        }
+        assert!(h.beds[&105].is_available());
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[BlockReason::AdjacentVip(10001)]
+        );
+        assert!(h.validate_invariants().is_empty());
+
+        // Later mutations keep working
+        h.admit_patient(&Patient::adult_male(10002, "P", 40), 105)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_remaining_capacity_for_profile() {
+        let mut h = Hospital::new();
+        assert_eq!(h.remaining_capacity_for(Gender::Male, 40), TOTAL_BEDS);
+        assert_eq!(h.remaining_capacity_for(Gender::Male, 8), 38); // Unit 5 only
+
+        let man = Patient::new(10001, "Man".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&man, 101).unwrap();
+        let male = h.remaining_capacity_for(Gender::Male, 40);
+        let female = h.remaining_capacity_for(Gender::Female, 40);
+        assert_eq!(male, TOTAL_BEDS - 1);
+        assert_eq!(female, TOTAL_BEDS - 2); // 102 only takes a man
+        assert!(male > female);
+
+        // Isolation unit: one patient per empty room
+        h.set_unit_isolation(2, true);
+        assert_eq!(
+            h.remaining_capacity_for(Gender::Female, 40),
+            TOTAL_BEDS - 2 - 19
+        );
+    }
+
+    #[test]
+    fn test_move_to_own_bed_is_a_no_op() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&vip, 101).unwrap();
+        let events = h.audit_log().len();
+        let admitted_at = h.admitted_at(10001);
+
+        assert_eq!(h.move_patient(10001, 101), Ok(()));
+        assert_eq!(h.audit_log().len(), events);
+        assert_eq!(h.admitted_at(10001), admitted_at);
+        assert!(h.beds[&102].is_blocked());
+    }
+
+    #[test]
+    fn test_tags_follow_the_patient() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 50, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 201).unwrap();
+
+        h.add_tag(10001, "fall-risk").unwrap();
+        h.add_tag(10001, "fall-risk").unwrap();
+        h.add_tag(10002, "NPO").unwrap();
+        assert_eq!(
+            h.add_tag(99999, "NPO"),
+            Err(HospitalError::PatientNotFound(99999))
+        );
+
+        h.move_patient(10001, 105).unwrap();
+        h.switch_patients(10001, 10002).unwrap();
+        let tagged = h.find_patients_with_tag("fall-risk");
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].0, 201);
+        assert_eq!(tagged[0].1.tags, vec!["fall-risk".to_string()]);
+        assert_eq!(h.find_patients_with_tag("NPO")[0].0, 105);
+
+        h.remove_tag(10001, "fall-risk").unwrap();
+        assert!(h.find_patients_with_tag("fall-risk").is_empty());
+    }
+
+    #[test]
+    fn test_infection_report() {
+        let mut h = Hospital::new();
+        let p1 = Patient::new(10001, "P1".into(), 40, Gender::Male, false, false);
+        let p2 = Patient::new(10002, "P2".into(), 50, Gender::Female, false, false);
+        let p3 = Patient::new(10003, "P3".into(), 60, Gender::Male, false, false);
+        h.admit_patient(&p1, 101).unwrap();
+        h.admit_patient(&p2, 202).unwrap();
+        h.admit_patient(&p3, 401).unwrap();
+        assert!(h.infection_report().is_empty());
+
+        h.mark_patient_as_infected(10002).unwrap();
+        h.mark_patient_as_infected(10001).unwrap();
+        assert_eq!(
+            h.infection_report(),
+            vec![
+                InfectionEntry {
+                    clinical_record: 10001,
+                    name: "P1".into(),
+                    bed: 101,
+                    blocked_bed: Some(102),
+                },
+                InfectionEntry {
+                    clinical_record: 10002,
+                    name: "P2".into(),
+                    bed: 202,
+                    blocked_bed: Some(201),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discharge_patient_if_present() {
+        let mut h = Hospital::new();
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+
+        assert!(h.discharge_patient_if_present(10001));
+        assert!(!h.discharge_patient_if_present(10001));
+        assert!(h.beds[&101].is_available());
+    }
+
+    #[test]
+    fn test_subscribers_receive_events() {
+        let mut h = Hospital::new();
+        let first = h.subscribe();
+        let second = h.subscribe();
+
+        let p = Patient::new(10001, "P".into(), 30, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+
+        for receiver in [&first, &second] {
+            assert!(matches!(
+                receiver.try_recv(),
+                Ok(AuditEvent::Admitted {
+                    clinical_record: 10001,
+                    bed: 101,
+                    ..
+                })
+            ));
+            assert!(receiver.try_recv().is_err());
+        }
+
+        // Dropped receivers are forgotten
+        drop(second);
+        h.discharge_patient(10001).unwrap();
+        assert_eq!(h.subscribers.len(), 1);
+        assert!(matches!(
+            first.try_recv(),
+            Ok(AuditEvent::Discharged { .. })
+        ));
+    }
+
+    #[test]
+    fn test_least_occupied_unit() {
+        let mut h = Hospital::new();
+        let adult = Patient::new(19999, "Adult".into(), 40, Gender::Male, false, false);
+        let child = Patient::new(19998, "Child".into(), 6, Gender::Male, false, false);
+        assert_eq!(h.least_occupied_unit(&adult), Some(1)); // All equal: lowest unit
+        assert_eq!(h.least_occupied_unit(&child), Some(5));
+
+        // Fill most of units 1, 2 and 5
+        let mut crn = 10000;
+        for unit in [1, 2, 5] {
+            for idx in 1..=30 {
+                let p = Patient::new(crn, "P".into(), 40, Gender::Male, false, false);
+                h.admit_patient(&p, unit * 100 + idx).unwrap();
+                crn += 1;
+            }
+        }
+        assert_eq!(h.least_occupied_unit(&adult), Some(4));
+        assert_eq!(h.least_occupied_unit(&child), Some(5));
+    }
+
+    #[test]
+    fn test_cycle_bed_cleans_then_frees_it() {
+        use crate::domain::clock::ManualClock;
+        use std::time::UNIX_EPOCH;
+
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000_000));
+        let mut h = Hospital::new();
+        h.set_clock(Box::new(clock.clone()));
+        let p = Patient::new(10001, "P".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&p, 101).unwrap();
+
+        h.cycle_bed(101, Duration::from_secs(30 * 60)).unwrap();
+        assert_eq!(h.find_patient_info(10001), None);
+        assert!(h.beds[&101].is_in_maintenance());
+        assert_eq!(
+            h.cycle_bed(101, Duration::from_secs(60)),
+            Err(HospitalError::BedNotOccupied(101))
+        );
+
+        clock.advance(Duration::from_secs(10 * 60));
+        assert!(h.expire_maintenance().is_empty());
+        assert!(h.beds[&101].is_in_maintenance());
+
+        clock.advance(Duration::from_secs(20 * 60));
+        assert_eq!(h.expire_maintenance(), vec![101]);
+        assert!(h.beds[&101].is_available());
+        assert!(h.expire_maintenance().is_empty());
+    }
+
+    #[test]
+    fn test_available_beds_for_all() {
+        let mut h = Hospital::new();
+        let man = Patient::new(10001, "Man".into(), 40, Gender::Male, false, false);
+        h.admit_patient(&man, 101).unwrap();
+
+        let child = Patient::new(20001, "Child".into(), 6, Gender::Female, false, false);
+        let male = Patient::new(20002, "Male".into(), 50, Gender::Male, false, false);
+        let female = Patient::new(20003, "Female".into(), 50, Gender::Female, false, false);
+        let patients = [child.clone(), male.clone(), female.clone()];
+
+        let available = h.available_beds_for_all(&patients);
+        assert_eq!(available.len(), 3);
+        for patient in &patients {
+            assert_eq!(
+                available[&patient.clinical_record_number],
+                h.get_available_beds_for_patient(patient)
+            );
+        }
+        assert!(available[&20001]
+            .iter()
+            .all(|n| n / 100 == u32::from(PEDIATRIC_UNIT)));
+        assert!(available[&20002].contains(&102));
+        assert!(!available[&20003].contains(&102));
+    }
+
+    #[test]
+    fn test_reclaim_orphaned_blocks() {
+        let mut h = Hospital::new();
+        let vip = Patient::new(10001, "VIP".into(), 50, Gender::Male, false, true);
+        h.admit_patient(&vip, 101).unwrap(); // Blocks 102
+        h.block_bed(104, BlockReason::Other).unwrap();
+        assert!(h.reclaim_orphaned_blocks().is_empty());
+
+        // Drop the VIP flag behind the hospital's back: 102 is now an orphan
+        if let BedState::Occupied(p) = &mut h.beds.get_mut(&101).unwrap().state {
+            p.is_vip = false;
+        }
+        h.beds.get_mut(&106).unwrap().state = BedState::Blocked {
+            reasons: vec![BlockReason::AdjacentInfected(10002), BlockReason::Other],
+        };
+
+        assert_eq!(h.reclaim_orphaned_blocks(), vec![102]);
+        assert!(h.beds[&102].is_available());
+        assert_eq!(h.beds[&104].block_reasons(), &[BlockReason::Other]);
+        assert_eq!(h.beds[&106].block_reasons(), &[BlockReason::Other]);
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_family_room_allows_mixed_gender() {
+        let mut h = Hospital::new().with_family_rooms(&[104]);
+        let man = Patient::adult_male(10001, "Man", 70);
+        let woman = Patient::adult_female(10002, "Woman", 68);
+        let infected = Patient::infected(10003, "Infected", 40, Gender::Female);
+
+        assert!(h.is_family_room(103) && h.is_family_room(104));
+        h.admit_patient(&man, 103).unwrap();
+        h.admit_patient(&woman, 104).unwrap();
+        assert!(h.validate_invariants().is_empty());
+
+        // Only the gender rule is relaxed, and only there
+        h.discharge_patient(10002).unwrap();
+        assert_eq!(
+            h.admit_patient(&infected, 104),
+            Err(HospitalError::RoomIncompatible {
+                bed: 103,
+                clinical_record: 10001,
+                reason: Box::new(HospitalError::AdjacentBedNotFree),
+            })
+        );
+        h.admit_patient(&woman, 201).unwrap();
+        let other_man = Patient::adult_male(10004, "Other man", 50);
+        assert_eq!(
+            h.admit_patient(&other_man, 202),
+            Err(HospitalError::RoomIncompatible {
+                bed: 201,
+                clinical_record: 10002,
+                reason: Box::new(HospitalError::GenderMismatch),
+            })
+        );
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 50, Gender::Male), 101)
+            .unwrap();
+
+        let grid = h.grid();
+        assert_eq!(grid.len(), VALID_UNITS.len());
+        let units: Vec<u16> = grid.iter().map(|(unit, _)| *unit).collect();
+        assert_eq!(units, VALID_UNITS.to_vec());
+        for (_, cells) in &grid {
+            assert_eq!(
+                cells.len(),
+                usize::from(LAST_BED_INDEX - FIRST_BED_INDEX + 1)
+            );
+        }
+        assert_eq!(
+            &grid[0].1[..3],
+            &[
+                (101, BedStateKind::Occupied),
+                (102, BedStateKind::Blocked),
+                (103, BedStateKind::Vacant),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_patient_cannot_be_admitted_twice() {
+        let mut h = Hospital::new();
+        let p = Patient::adult_male(10001, "P", 40);
+        h.admit_patient(&p, 101).unwrap();
+
+        assert_eq!(
+            h.admit_patient(&p, 201),
+            Err(HospitalError::PatientAlreadyAdmitted(101))
+        );
+        assert!(h.beds[&201].is_available());
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+    }
+
+    #[test]
+    fn test_patient_history() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "P", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "Other", 50), 201)
+            .unwrap();
+        h.set_maintenance(105, true).unwrap(); // Not tied to a patient
+        h.move_patient(10001, 103).unwrap();
+        h.discharge_patient(10001).unwrap();
+
+        let history = h.patient_history(10001);
+        assert_eq!(history.len(), 3);
+        assert!(matches!(history[0], AuditEvent::Admitted { bed: 101, .. }));
+        assert!(matches!(
+            history[1],
+            AuditEvent::Moved {
+                from: 101,
+                to: 103,
+                ..
+            }
+        ));
+        assert!(matches!(
+            history[2],
+            AuditEvent::Discharged { bed: 103, .. }
+        ));
+        assert!(h.patient_history(99999).is_empty());
+    }
+
+    #[test]
+    fn test_apply_matches_direct_calls() {
+        let p1 = Patient::adult_male(10001, "P1", 40);
+        let p2 = Patient::adult_male(10002, "P2", 50);
+        let commands = vec![
+            Command::Admit {
+                patient: p1.clone(),
+                bed: 101,
+            },
+            Command::Admit {
+                patient: p2.clone(),
+                bed: 102,
+            },
+            Command::MarkInfected {
+                clinical_record: 10001,
+            },
+            Command::Move {
+                clinical_record: 10001,
+                to: 999,
+            },
+            Command::Discharge {
+                clinical_record: 10001,
+            },
+        ];
+
+        let mut replayed = Hospital::new();
+        let outcomes: Vec<_> = commands
+            .into_iter()
+            .map(|command| replayed.apply(command))
+            .collect();
+
+        let mut direct = Hospital::new();
+        direct.admit_patient(&p1, 101).unwrap();
+        direct.admit_patient(&p2, 102).unwrap();
+        let relocated = direct.mark_patient_as_infected(10001).unwrap();
+        let failed_move = direct.move_patient(10001, 999).unwrap_err();
+        let discharged = direct.discharge_patient(10001).unwrap();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(CommandOutcome::Done),
+                Ok(CommandOutcome::Done),
+                Ok(CommandOutcome::Relocated(relocated)),
+                Err(failed_move),
+                Ok(CommandOutcome::Discharged(discharged)),
+            ]
+        );
+        assert_eq!(replayed.snapshot(), direct.snapshot());
+        assert!(replayed.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_legal_operations_keep_invariants() {
+        // Every operation asserts the invariants in debug builds: this must not panic
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "P1", 40), 201)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "P2", 50), 202)
+            .unwrap();
+        h.set_unit_isolation(2, true); // P1 and P2 keep sharing
+        h.admit_patient(&Patient::adult_female(10003, "P3", 60), 203)
+            .unwrap(); // Blocks 204
+        h.set_unit_isolation(2, false);
+        assert!(h.beds[&204].is_available());
+
+        h.admit_patient(&Patient::vip(10004, "VIP", 70, Gender::Male), 101)
+            .unwrap();
+        h.mark_patient_as_infected(10001).unwrap(); // P2 relocated
+        h.switch_patients(10001, 10004).unwrap();
+        h.set_patient_vip(10004, false).unwrap();
+        h.unmark_patient_as_infected(10001).unwrap();
+        h.move_patient(10003, 401).unwrap();
+        h.set_maintenance(402, true).unwrap();
+        h.block_bed(403, BlockReason::Other).unwrap();
+        h.assign_private_room(10002).unwrap();
+        h.discharge_patient(10002).unwrap();
+        h.discharge_unit(4);
+
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_next_likely_free_bed_predicts_earliest_admission() {
+        use crate::domain::clock::ManualClock;
+        use std::time::UNIX_EPOCH;
+
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000_000));
+        let mut h = Hospital::new();
+        h.set_clock(Box::new(clock.clone()));
+        h.set_average_length_of_stay(4 * DAY);
+
+        let beds = h.beds_in_unit(1);
+        let (first, rest) = beds.split_first().unwrap();
+        h.admit_patient(&Patient::adult_male(10001, "Long stay", 40), *first)
+            .unwrap();
+        clock.advance(3 * DAY);
+        for (i, &n) in rest.iter().enumerate() {
+            let p = Patient::adult_male(10002 + i as u32, "Short stay", 40);
+            h.admit_patient(&p, n).unwrap();
+        }
+
+        // Full unit: the patient who has stayed 3 days goes first, in 1 day
+        assert_eq!(h.next_likely_free_bed(1), Some((*first, DAY)));
+
+        clock.advance(2 * DAY); // Overdue
+        assert_eq!(h.next_likely_free_bed(1), Some((*first, Duration::ZERO)));
+
+        // A vacant bed is free now, ahead of everyone still expected to leave
+        h.discharge_patient(10001).unwrap();
+        assert_eq!(h.next_likely_free_bed(1), Some((*first, Duration::ZERO)));
+        h.admit_patient(&Patient::adult_male(10001, "Again", 40), *first)
+            .unwrap();
+        assert_eq!(h.next_likely_free_bed(1), Some((rest[0], 2 * DAY)));
+        assert_eq!(h.next_likely_free_bed(3), None);
+    }
+
+    /// A hospital with every bed taken (children in the pediatric unit, men elsewhere)
+    fn full_hospital() -> Hospital {
+        let mut h = Hospital::new();
+        for (i, n) in h.sorted_bed_numbers().into_iter().enumerate() {
+            let crn = 10000 + i as u32;
+            let p = if n / 100 == u32::from(PEDIATRIC_UNIT) {
+                Patient::new(crn, "Child".into(), 8, Gender::Male, false, false)
+            } else {
+                Patient::adult_male(crn, "Adult", 40)
+            };
+            h.admit_patient(&p, n).unwrap();
+        }
+        h
+    }
+
+    #[test]
+    fn test_relocation_failure_aborts_by_default() {
+        let mut h = full_hospital();
+        assert_eq!(
+            h.mark_patient_as_infected(10000),
+            Err(HospitalError::NoBedForRoommate)
+        );
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 102);
+        assert!(!h.find_patient_info(10000).unwrap().1.is_infected);
+        assert!(h.waitlist().is_empty());
+    }
+
+    #[test]
+    fn test_relocation_failure_can_waitlist_roommate() {
+        let mut h = full_hospital();
+        h.set_relocation_failure_policy(RelocationFailurePolicy::WaitlistRoommate);
+
+        assert_eq!(h.mark_patient_as_infected(10000), Ok(None));
+        assert!(h.find_patient_info(10000).unwrap().1.is_infected);
+        assert_eq!(h.find_patient_info(10001), None);
+        assert!(h.beds[&102].is_blocked());
+        let waiting: Vec<u32> = h
+            .waitlist()
+            .iter()
+            .map(|p| p.clinical_record_number)
+            .collect();
+        assert_eq!(waiting, vec![10001]);
+
+        assert_eq!(h.set_patient_vip(10002, true), Ok(None));
+        assert_eq!(h.waitlist().len(), 2);
+        assert!(h.beds[&104].is_blocked());
+    }
+
+    #[test]
+    fn test_female_only_unit_rejects_men() {
+        let mut h = Hospital::new();
+        h.set_unit_gender(2, Some(Gender::Female));
+        let man = Patient::adult_male(10001, "Man", 40);
+        let woman = Patient::adult_female(10002, "Woman", 40);
+
+        assert_eq!(
+            h.admit_patient(&man, 201),
+            Err(HospitalError::UnitGenderMismatch(2))
+        );
+        assert!(h
+            .get_available_beds_for_patient(&man)
+            .iter()
+            .all(|&n| n / 100 != 2));
+        h.admit_patient(&woman, 203).unwrap();
+        h.admit_patient(&man, 101).unwrap();
+        assert_eq!(
+            h.switch_patients(10001, 10002),
+            Err(HospitalError::UnitGenderMismatch(2))
+        );
+
+        h.set_unit_gender(2, None);
+        assert_eq!(h.unit_gender(2), None);
+        h.move_patient(10001, 201).unwrap();
+    }
+
+    #[test]
+    fn test_admit_patient_or_alternate_uses_preferred_bed() {
+        let mut h = Hospital::new();
+        let p = Patient::adult_male(10001, "P", 40);
+        assert_eq!(h.admit_patient_or_alternate(&p, 207), Ok(207));
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 207);
+        assert_eq!(
+            h.admit_patient_or_alternate(&p, 209),
+            Err(HospitalError::PatientAlreadyAdmitted(207))
+        );
+    }
+
+    #[test]
+    fn test_admit_patient_or_alternate_falls_back() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap(); // Blocks 102
+
+        let p = Patient::adult_male(10002, "P", 40);
+        let bed = h.admit_patient_or_alternate(&p, 102).unwrap();
+        assert_eq!(Some(bed), h.find_patient_info(10002).map(|(n, _)| n));
+        assert_ne!(bed, 102);
+
+        let child = Patient::new(20001, "Child".into(), 8, Gender::Female, false, false);
+        let bed = h.admit_patient_or_alternate(&child, 101).unwrap();
+        assert_eq!(h.unit_of(bed), PEDIATRIC_UNIT);
+
+        let other = Patient::adult_male(10003, "Other", 40);
+        assert_eq!(
+            h.admit_patient_or_alternate(&other, 301),
+            Err(HospitalError::BedNotFound(301))
+        );
+
+        // No pediatric space left: the child can't go anywhere
+        for n in h.beds_in_unit(PEDIATRIC_UNIT) {
+            if h.beds[&n].is_available() {
+                h.set_maintenance(n, true).unwrap();
+            }
+        }
+        let child2 = Patient::new(20002, "Child 2".into(), 8, Gender::Male, false, false);
+        assert_eq!(
+            h.admit_patient_or_alternate(&child2, 101),
+            Err(HospitalError::NoCompatibleBed)
+        );
+    }
+
+    #[test]
+    fn test_patients_by_room_groups_roommates() {
+        let mut h = Hospital::new();
+        let a = Patient::adult_female(10001, "A", 40);
+        let b = Patient::adult_female(10002, "B", 50);
+        let c = Patient::adult_male(10003, "C", 60);
+        h.admit_patient(&c, 405).unwrap();
+        h.admit_patient(&b, 102).unwrap();
+        h.admit_patient(&a, 101).unwrap();
+
+        assert_eq!(
+            h.patients_by_room(),
+            vec![
+                RoomOccupancy {
+                    beds: vec![(101, Some(a)), (102, Some(b))],
+                },
+                RoomOccupancy {
+                    beds: vec![(405, Some(c)), (406, None)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_units_over_threshold() {
+        let mut h = Hospital::new();
+        let beds = h.beds_in_unit(1);
+        let ninety_pct = beds.len() * 9 / 10;
+        for (i, &n) in beds.iter().take(ninety_pct).enumerate() {
+            h.admit_patient(&Patient::adult_male(10001 + i as u32, "P", 40), n)
+                .unwrap();
+        }
+
+        assert_eq!(h.units_over_threshold(0.8), vec![1]);
+        assert!(h.units_over_threshold(0.95).is_empty());
+
+        // Blocked beds leave the denominator
+        for &n in &beds[beds.len() - 3..] {
+            h.block_bed(n, BlockReason::Other).unwrap();
+        }
+        assert_eq!(h.units_over_threshold(0.95), vec![1]);
+    }
+
+    #[test]
+    fn test_room_incompatible_names_the_occupant() {
+        let mut h = Hospital::with_beds_per_room(4);
+        h.admit_patient(&Patient::adult_male(10001, "Man", 40), 102)
+            .unwrap();
+        let woman = Patient::adult_female(10002, "Woman", 40);
+
+        let err = h.admit_patient(&woman, 104).unwrap_err();
+        assert_eq!(
+            err,
+            HospitalError::RoomIncompatible {
+                bed: 102,
+                clinical_record: 10001,
+                reason: Box::new(HospitalError::GenderMismatch),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Incompatible with patient 10001 in bed 102: Roommates must have the same gender"
+        );
+    }
+
+    #[test]
+    fn test_chain_move_swaps_through_temp_bed() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "A", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "B", 50), 201)
+            .unwrap();
+
+        h.chain_move(&[(10001, 105), (10002, 101), (10001, 201)])
+            .unwrap();
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 201);
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 101);
+        assert!(h.beds[&105].is_available());
+    }
+
+    #[test]
+    fn test_chain_move_rolls_back_on_failure() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "A", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::vip(10002, "VIP", 50, Gender::Male), 201)
+            .unwrap(); // Blocks 202
+
+        assert_eq!(
+            h.chain_move(&[(10001, 105), (10002, 101), (10001, 102)]),
+            Err(HospitalError::BedNotAvailable(102)) // Now blocked by the VIP
+        );
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 201);
+        assert!(h.beds[&202].is_blocked());
+        assert!(h.beds[&102].is_available());
+        assert!(h.beds[&105].is_available());
+        assert_eq!(
+            h.chain_move(&[(10001, 105), (99999, 101)]),
+            Err(HospitalError::PatientNotFound(99999))
+        );
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+    }
+
+    #[test]
+    fn test_chain_move_rolls_back_moves_that_cant_be_reversed() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "A", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "B", 50), 102)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10003, "C", 60), 103)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10004, "D", 70), 201)
+            .unwrap();
+        // Moving A back to 101 is no longer allowed: the unit is isolated, so A would need
+        // the room to themselves, and only one man fits under the cap
+        h.set_unit_isolation(1, true);
+        h.set_gender_cap(1, Gender::Male, 1);
+        let events = h.subscribe();
+        let log_len = h.audit_log().len();
+
+        assert_eq!(
+            h.chain_move(&[(10001, 205), (10004, 102)]),
+            Err(HospitalError::BedNotAvailable(102))
+        );
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+        assert_eq!(h.find_patient_info(10004).unwrap().0, 201);
+        assert!(h.bed(205).unwrap().is_available());
+        assert_eq!(h.audit_log().len(), log_len);
+        assert_eq!(events.try_iter().count(), 0);
+        assert!(h.validate_invariants().is_empty());
+
+        h.chain_move(&[(10001, 205), (10004, 207)]).unwrap();
+        assert_eq!(h.audit_log().len(), log_len + 2);
+        assert_eq!(events.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_telemetry_patient_only_offered_telemetry_beds() {
+        let mut h = Hospital::new()
+            .with_bed_capability(BedCapability::Telemetry, &[103, 205])
+            .with_bed_capability(BedCapability::Bariatric, &[205, 401]);
+        let mut p = Patient::adult_male(10001, "Cardiac", 60);
+        p.requirements = vec![BedCapability::Telemetry];
+
+        assert_eq!(h.get_available_beds_for_patient(&p), vec![103, 205]);
+        assert_eq!(
+            h.admit_patient(&p, 101),
+            Err(HospitalError::MissingBedCapability {
+                bed: 101,
+                capability: BedCapability::Telemetry,
+            })
+        );
+
+        p.requirements.push(BedCapability::Bariatric);
+        assert_eq!(h.get_available_beds_for_patient(&p), vec![205]);
+        assert_eq!(h.recommend_bed(&p), Some(205));
+        h.admit_patient(&p, 205).unwrap();
+    }
+
+    #[test]
+    fn test_revoke_all_vip_frees_neighbours() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP 1", 60, Gender::Male), 101)
+            .unwrap();
+        h.admit_patient(&Patient::vip(10002, "VIP 2", 50, Gender::Female), 203)
+            .unwrap();
+        let mut infected_vip = Patient::vip(10003, "Infected VIP", 70, Gender::Male);
+        infected_vip.is_infected = true;
+        h.admit_patient(&infected_vip, 401).unwrap();
+
+        assert_eq!(h.revoke_all_vip(), vec![(10001, 102), (10002, 204)]);
+        assert_eq!(h.count_vip(), 0);
+        assert!(h.beds[&102].is_available() && h.beds[&204].is_available());
+        assert_eq!(
+            h.beds[&402].block_reasons(),
+            &[BlockReason::AdjacentInfected(10003)]
+        );
+        assert!(h.revoke_all_vip().is_empty());
+    }
+
+    #[test]
+    fn test_move_patient_missing_patient_vs_bad_destination() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "P", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::vip(10002, "VIP", 60, Gender::Male), 201)
+            .unwrap(); // Blocks 202
+        let log_len = h.audit_log().len();
+        let before = h.count_beds_by_state();
+
+        // Unknown patient: reported as such even when the destination is bad too
+        for destination in [103, 202, 301] {
+            assert_eq!(
+                h.move_patient(99999, destination),
+                Err(HospitalError::PatientNotFound(99999))
+            );
+        }
+        assert_eq!(h.count_beds_by_state(), before);
+        assert_eq!(h.audit_log().len(), log_len);
+
+        // Known patient, bad destination
+        assert_eq!(
+            h.move_patient(10001, 301),
+            Err(HospitalError::BedNotFound(301))
+        );
+        assert_eq!(
+            h.move_patient(10001, 202),
+            Err(HospitalError::BedNotAvailable(202))
+        );
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 101);
+        assert_eq!(h.count_beds_by_state(), before);
+        assert_eq!(h.audit_log().len(), log_len);
+    }
+
+    #[test]
+    fn test_high_priority_clean_beds() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "P1", 40), 203)
+            .unwrap();
+        h.admit_patient(&Patient::vip(10002, "VIP", 60, Gender::Male), 101)
+            .unwrap();
+        assert!(h.high_priority_clean_beds().is_empty());
+
+        h.mark_patient_as_infected(10001).unwrap();
+        h.admit_patient(&Patient::infected(10003, "P3", 40, Gender::Female), 409)
+            .unwrap();
+        assert_eq!(h.high_priority_clean_beds(), vec![204, 410]);
+
+        h.unmark_patient_as_infected(10001).unwrap();
+        assert_eq!(h.high_priority_clean_beds(), vec![410]);
+    }
+
+    #[test]
+    fn test_gender_cap_per_unit() {
+        let mut h = Hospital::new();
+        h.set_gender_cap(2, Gender::Male, 1);
+        let first = Patient::adult_male(10001, "First", 40);
+        let second = Patient::adult_male(10002, "Second", 50);
+
+        h.admit_patient(&first, 201).unwrap();
+        assert_eq!(
+            h.admit_patient(&second, 205),
+            Err(HospitalError::GenderCapReached(2))
+        );
+        assert!(h
+            .get_available_beds_for_patient(&second)
+            .iter()
+            .all(|&n| n / 100 != 2));
+        h.move_patient(10001, 207).unwrap(); // Already counted: can move within the unit
+        h.admit_patient(&Patient::adult_female(10003, "Woman", 40), 203)
+            .unwrap();
+        assert_eq!(h.remaining_capacity_for(Gender::Male, 40), 3 * 38); // None in unit 2
+
+        h.discharge_patient(10001).unwrap();
+        h.admit_patient(&second, 205).unwrap();
+    }
+
+    #[test]
+    fn test_switch_keeps_gender_cap_count() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "A", 40), 101)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "B", 50), 201)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10003, "C", 60), 103)
+            .unwrap();
+        h.set_gender_cap(2, Gender::Male, 1);
+
+        // Unit 2 still holds one man after the swap
+        h.switch_patients(10001, 10002).unwrap();
+        assert_eq!(h.find_patient_info(10001).unwrap().0, 201);
+        assert_eq!(h.find_patient_info(10002).unwrap().0, 101);
+
+        // Swapping a man in for a woman would make two
+        h.admit_patient(&Patient::adult_female(10004, "D", 45), 203)
+            .unwrap();
+        assert_eq!(
+            h.switch_patients(10003, 10004),
+            Err(HospitalError::GenderCapReached(2))
+        );
+    }
+
+    #[test]
+    fn test_same_room() {
+        let h = Hospital::new().with_single_rooms(&[105]);
+        assert!(h.same_room(101, 102) && h.same_room(102, 101));
+        assert!(!h.same_room(101, 103));
+        assert!(!h.same_room(102, 103));
+        assert!(!h.same_room(105, 106));
+        assert!(h.same_room(105, 105));
+        assert!(!h.same_room(301, 302));
+
+        let bays = Hospital::with_beds_per_room(4);
+        assert!(bays.same_room(101, 103) && bays.same_room(101, 104));
+        assert!(!bays.same_room(104, 105));
+    }
+
+    #[test]
+    fn test_preempt_vip_block() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap(); // Blocks 102
+        h.admit_patient(&Patient::infected(10002, "Infected", 40, Gender::Male), 201)
+            .unwrap(); // Blocks 202
+
+        // Incompatible newcomer: nothing changes, and nothing is logged or announced
+        let events = h.subscribe();
+        let log_len = h.audit_log().len();
+        let woman = Patient::adult_female(10003, "Woman", 40);
+        assert!(matches!(
+            h.preempt_block(102, &woman),
+            Err(HospitalError::RoomIncompatible { .. })
+        ));
+        assert!(h.find_patient_info(10001).unwrap().1.is_vip);
+        assert_eq!(
+            h.beds[&102].block_reasons(),
+            &[BlockReason::AdjacentVip(10001)]
+        );
+        assert_eq!(h.audit_log().len(), log_len);
+        assert_eq!(events.try_iter().count(), 0);
+
+        let man = Patient::adult_male(10004, "Man", 50);
+        assert_eq!(
+            h.preempt_block(202, &man),
+            Err(HospitalError::BedNotAvailable(202))
+        );
+        assert_eq!(
+            h.preempt_block(103, &man),
+            Err(HospitalError::BedNotAvailable(103))
+        );
+
+        h.preempt_block(102, &man).unwrap();
+        assert!(!h.find_patient_info(10001).unwrap().1.is_vip);
+        assert_eq!(h.find_patient_info(10004).unwrap().0, 102);
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_compact_lazy_bed_map() {
+        let mut h = Hospital::new_lazy();
+        assert_eq!(h.beds.stored_len(), 0);
+        assert_eq!(h.bed_count(), TOTAL_BEDS);
+
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap(); // Blocks 102
+        assert_eq!(h.beds.stored_len(), 2);
+        h.discharge_patient(10001).unwrap();
+        assert_eq!(h.beds.stored_len(), 2); // Vacant entries left behind
+
+        h.compact();
+        assert_eq!(h.beds.stored_len(), 0);
+        assert_eq!(h.bed_state(101), Some(&BedState::Vacant));
+        assert_eq!(h.bed_state(102), Some(&BedState::Vacant));
+        assert_eq!(h.bed_state(301), None);
+        assert_eq!(h.count_beds_by_state(), (0, TOTAL_BEDS, 0, 0));
+    }
+
+    #[test]
+    fn test_lazy_mode_answers_like_eager_mode() {
+        let mut eager = Hospital::new();
+        let mut lazy = Hospital::new_lazy();
+        for h in [&mut eager, &mut lazy] {
+            h.admit_patient(&Patient::adult_male(10001, "P1", 40), 101)
+                .unwrap();
+            h.admit_patient(&Patient::infected(10002, "P2", 50, Gender::Female), 203)
+                .unwrap();
+            h.set_maintenance(405, true).unwrap();
+            h.discharge_patient(10001).unwrap();
+        }
+        lazy.compact();
+
+        assert_eq!(eager.snapshot(), lazy.snapshot());
+        let woman = Patient::adult_female(10003, "P3", 30);
+        assert_eq!(
+            eager.get_available_beds_for_patient(&woman),
+            lazy.get_available_beds_for_patient(&woman)
+        );
+        assert_eq!(eager.summary(), lazy.summary());
+    }
+
+    #[test]
+    fn test_expire_reservations_frees_bed_after_hold() {
+        use crate::domain::clock::ManualClock;
+        use std::time::UNIX_EPOCH;
+
+        let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1_000_000));
+        let mut h = Hospital::new();
+        h.set_clock(Box::new(clock.clone()));
+        let hour = Duration::from_secs(60 * 60);
+        let other = Patient::adult_male(10002, "Other", 40);
+
+        h.reserve_bed(101, 10001, Some(hour)).unwrap();
+        h.reserve_bed(103, 10003, None).unwrap();
+        assert_eq!(h.bed(101).unwrap().state.kind(), BedStateKind::Reserved);
+        assert_eq!(
+            h.admit_patient(&other, 101),
+            Err(HospitalError::BedNotAvailable(101))
+        );
+        assert!(!h.get_available_beds_for_patient(&other).contains(&101));
+        assert!(h.expire_reservations().is_empty());
+
+        clock.advance(2 * hour);
+        // Already expired: available before anyone calls expire_reservations
+        assert!(h.get_available_beds_for_patient(&other).contains(&101));
+        assert_eq!(h.expire_reservations(), vec![101]);
+        assert_eq!(h.bed_state(101), Some(&BedState::Vacant));
+        assert_eq!(h.bed(103).unwrap().state.kind(), BedStateKind::Reserved); // No expiry
+        assert!(h.expire_reservations().is_empty());
+        assert!(matches!(
+            h.audit_log().last(),
+            Some(AuditEvent::Reserved {
+                bed: 101,
+                clinical_record: 10001,
+                on: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_reserved_bed_admits_only_its_patient() {
+        let mut h = Hospital::new();
+        let vip = Patient::vip(10001, "VIP", 60, Gender::Male);
+        h.reserve_bed(101, 10001, None).unwrap();
+        h.reserve_bed(104, 10004, None).unwrap();
+
+        // The neighbour of a reserved bed isn't free to block
+        assert_eq!(
+            h.admit_patient(&vip, 103),
+            Err(HospitalError::AdjacentBedNotFree)
+        );
+        assert_eq!(
+            h.set_maintenance(101, true),
+            Err(HospitalError::BedNotAvailable(101))
+        );
+        assert_eq!(h.summary().reserved, 2);
+
+        h.admit_patient(&vip, 101).unwrap();
+        assert!(h.bed(102).unwrap().is_blocked());
+
+        // A reservation cancelled next to an infectious patient comes back blocked
+        h.admit_patient(&Patient::adult_male(10003, "P3", 50), 103)
+            .unwrap();
+        h.mark_patient_as_infected(10003).unwrap();
+        assert_eq!(h.bed(104).unwrap().state.kind(), BedStateKind::Reserved);
+        h.cancel_reservation(104).unwrap();
+        assert_eq!(
+            h.bed(104).unwrap().block_reasons(),
+            &[BlockReason::AdjacentInfected(10003)]
+        );
+        h.cancel_reservation(104).unwrap(); // Not reserved any more: nothing to do
+    }
+
+    #[test]
+    fn test_reserve_bed_checks_the_patient() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::adult_male(10001, "Admitted", 40), 101)
+            .unwrap();
+
+        assert_eq!(
+            h.reserve_bed(201, 123, None),
+            Err(HospitalError::InvalidClinicalRecord(123))
+        );
+        assert_eq!(
+            h.reserve_bed(201, 10001, None),
+            Err(HospitalError::PatientAlreadyAdmitted(101))
+        );
+
+        h.reserve_bed(201, 10002, Some(Duration::from_secs(60)))
+            .unwrap();
+        assert_eq!(
+            h.reserve_bed(203, 10002, None),
+            Err(HospitalError::PatientAlreadyReserved(201))
+        );
+        h.reserve_bed(201, 10002, None).unwrap(); // Same bed: replaces the hold
+        assert_eq!(h.summary().reserved, 1);
+
+        h.cancel_reservation(201).unwrap();
+        h.reserve_bed(203, 10002, None).unwrap();
+    }
+
+    #[test]
+    fn test_switch_patient_with_themselves_is_a_no_op() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap();
+        let before = h.snapshot();
+        let log_len = h.audit_log().len();
+
+        assert_eq!(h.switch_patients(10001, 10001), Ok(()));
+        assert_eq!(h.snapshot(), before);
+        assert_eq!(h.audit_log().len(), log_len);
+        assert_eq!(
+            h.switch_patients(10002, 10002),
+            Err(HospitalError::PatientNotFound(10002))
+        );
+    }
+
+    #[test]
+    fn test_layout_with_120_beds_per_unit() {
+        let mut h = Hospital::try_with_layout(&[1, 5], 120, 2).unwrap();
+        assert_eq!(h.bed_count(), 240);
+        assert_eq!(h.beds_in_unit(1).first(), Some(&1001));
+        assert!(h.bed(1120).is_some());
+        assert!(h.bed(1121).is_none() && h.bed(101).is_none());
+        assert_eq!(h.unit_of(1120), 1);
+        assert_eq!(h.roommate_of_bed(1120), Some(1119));
+        assert_eq!(Hospital::roommate_of(1120), 1119);
+
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 1120)
+            .unwrap();
+        assert_eq!(h.beds_blocked_by(10001), vec![1119]);
+
+        let child = Patient::new(10002, "Child".into(), 8, Gender::Female, false, false);
+        assert_eq!(
+            h.admit_patient(&child, 1001),
+            Err(HospitalError::PediatricUnitRequired)
+        );
+        h.admit_patient(&child, 5120).unwrap();
+        assert_eq!(h.get_available_beds_for_patient(&child).len(), 119);
+    }
+
+    #[test]
+    fn test_turning_off_vip_blocking_frees_their_neighbours() {
+        let mut h = Hospital::new();
+        h.admit_patient(&Patient::vip(10001, "VIP", 60, Gender::Male), 101)
+            .unwrap();
+        assert!(h.bed(102).unwrap().is_blocked());
+
+        h.set_admission_rules(AdmissionRules {
+            vip_blocks_adjacent: false,
+            ..AdmissionRules::default()
+        })
+        .unwrap();
+        assert!(h.bed(102).unwrap().is_available());
+        assert!(h.validate_invariants().is_empty());
+
+        // Later mutations keep working
+        h.admit_patient(&Patient::adult_male(10002, "Roommate", 50), 102)
+            .unwrap();
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_admission_rules_rejected_when_admitted_patients_break_them() {
+        let mut h = Hospital::new();
+        let teen = Patient::new(10001, "Teen".into(), 16, Gender::Female, false, false);
+        h.admit_patient(&teen, 101).unwrap();
+        h.admit_patient(&Patient::adult_male(10002, "A", 40), 201)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10003, "B", 50), 202)
+            .unwrap();
+
+        let pediatric_18 = AdmissionRules {
+            pediatric_max_age: 18,
+            ..AdmissionRules::default()
+        };
+        assert_eq!(
+            h.set_admission_rules(pediatric_18),
+            Err(HospitalError::RulesViolatedBy {
+                bed: 101,
+                clinical_record: 10001,
+                reason: Box::new(HospitalError::PediatricUnitRequired),
+            })
+        );
+        assert_eq!(h.admission_rules(), &AdmissionRules::default());
+
+        // Patients already sharing a room when VIPs start blocking it are in the way too
+        h.set_admission_rules(AdmissionRules {
+            vip_blocks_adjacent: false,
+            ..AdmissionRules::default()
+        })
+        .unwrap();
+        h.admit_patient(&Patient::vip(10004, "VIP", 60, Gender::Male), 203)
+            .unwrap();
+        h.admit_patient(&Patient::adult_male(10005, "C", 45), 204)
+            .unwrap();
+        assert_eq!(
+            h.set_admission_rules(AdmissionRules::default()),
+            Err(HospitalError::RulesViolatedBy {
+                bed: 203,
+                clinical_record: 10004,
+                reason: Box::new(HospitalError::RoommateRequiresIsolation),
+            })
+        );
+
+        // Nothing was left half-changed: admitting still works
+        h.admit_patient(&Patient::adult_male(10006, "D", 30), 205)
+            .unwrap();
+        assert!(h.validate_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_room_members_for_configured_room_size() {
+        let h = Hospital::new();
+        assert_eq!(h.room_members(101), vec![101, 102]);
+        assert_eq!(h.room_members(102), vec![101, 102]);
+        assert!(h.room_members(301).is_empty());
+
+        let h = Hospital::with_beds_per_room(4);
+        assert_eq!(h.room_members(103), vec![101, 102, 103, 104]);
+        // 38 beds per unit: the last bay only has two beds
+        assert_eq!(h.room_members(538), vec![537, 538]);
+    }
+
+    #[test]
+    fn test_four_bed_room_checks_every_occupant() {
+        let mut h = Hospital::with_beds_per_room(4);
+        let m1 = Patient::new(10001, "M1".into(), 30, Gender::Male, false, false);
+        let m2 = Patient::new(10002, "M2".into(), 40, Gender::Male, false, false);
+        let m3 = Patient::new(10003, "M3".into(), 50, Gender::Male, false, false);
+        let f4 = Patient::new(10004, "F4".into(), 35, Gender::Female, false, false);
+
+        h.admit_patient(&m1, 101).unwrap();
+        h.admit_patient(&m2, 102).unwrap();
+        // Third compatible patient fits in the bay
+        assert!(h.admit_patient(&m3, 103).is_ok());
+        // Fourth is incompatible with every occupant
+        assert!(h
+            .get_available_beds_for_patient(&f4)
+            .iter()
+            .all(|&b| b > 104));
+        assert!(h.admit_patient(&f4, 104).is_err());
+        assert!(h.beds[&104].is_available());
+    }
 }
@@ -0,0 +1,21 @@
+/// A broken rule found by `Hospital::validate_invariants` (state that the operations
+/// themselves should never produce)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A blocked bed names a patient who isn't in its room to justify the block
+    UnjustifiedBlock { bed: u32, clinical_record: u32 },
+    /// Roommates of different gender
+    GenderMismatch { bed: u32, roommate: u32 },
+    /// A minor sharing a room with an adult
+    AgeGroupMismatch { bed: u32, roommate: u32 },
+    /// Someone sharing a room with a VIP/infectious patient (the one in `bed`)
+    SharedIsolationRoom { bed: u32, roommate: u32 },
+    /// A pediatric patient outside the pediatric unit
+    ChildOutsidePediatricUnit { bed: u32, clinical_record: u32 },
+    /// The same patient in a second bed (`first_bed` is where they were found first)
+    DuplicateClinicalRecord {
+        bed: u32,
+        first_bed: u32,
+        clinical_record: u32,
+    },
+}
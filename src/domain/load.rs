@@ -0,0 +1,112 @@
+use crate::domain::constants::PEDIATRIC_MAX_AGE;
+use crate::domain::patient::{Gender, Patient};
+
+/// First clinical record number handed out by the generator (must have 5 digits)
+const FIRST_LOAD_CRN: u32 = 10000;
+
+/// Most patients `generate_load` can produce: one per 5-digit clinical record number
+pub const MAX_LOAD_PATIENTS: usize = 90000;
+
+/// Small deterministic PRNG (SplitMix64), good enough for load testing
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in lo..hi (hi exclusive)
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next_u64() % (hi - lo)
+    }
+
+    /// True with the given probability in percent (0..=100)
+    fn chance(&mut self, pct: u8) -> bool {
+        self.range(0, 100) < u64::from(pct)
+    }
+}
+
+/// Generates a reproducible stream of patients for load testing.
+/// - Same seed → same patients (CRNs are sequential from 10000).
+/// - At most `MAX_LOAD_PATIENTS` patients: a larger `count` is clamped, since CRNs past
+///   99999 aren't valid.
+/// - `vip_pct`, `infected_pct` and `child_pct` are percentages (0..=100).
+/// - Children are under `PEDIATRIC_MAX_AGE`; everyone else is up to 90.
+pub fn generate_load(
+    seed: u64,
+    count: usize,
+    vip_pct: u8,
+    infected_pct: u8,
+    child_pct: u8,
+) -> Vec<Patient> {
+    let mut rng = SplitMix64::new(seed);
+
+    (0..count.min(MAX_LOAD_PATIENTS))
+        .map(|i| {
+            let clinical_record_number = FIRST_LOAD_CRN + i as u32;
+            let age = if rng.chance(child_pct) {
+                rng.range(0, u64::from(PEDIATRIC_MAX_AGE))
+            } else {
+                rng.range(u64::from(PEDIATRIC_MAX_AGE), 91)
+            } as u8;
+            let gender = if rng.chance(50) {
+                Gender::Male
+            } else {
+                Gender::Female
+            };
+
+            Patient::new(
+                clinical_record_number,
+                format!("Load Patient {}", clinical_record_number),
+                age,
+                gender,
+                rng.chance(infected_pct),
+                rng.chance(vip_pct),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_load_child_ratio() {
+        let patients = generate_load(42, 100, 0, 0, 10);
+        assert_eq!(patients.len(), 100);
+
+        let children = patients
+            .iter()
+            .filter(|p| p.age < PEDIATRIC_MAX_AGE)
+            .count();
+        assert!((4..=16).contains(&children), "children = {}", children);
+    }
+
+    #[test]
+    fn test_generate_load_clamps_to_valid_crns() {
+        let patients = generate_load(1, MAX_LOAD_PATIENTS + 10, 0, 0, 0);
+        assert_eq!(patients.len(), MAX_LOAD_PATIENTS);
+        assert_eq!(patients.last().unwrap().clinical_record_number, 99999);
+    }
+
+    #[test]
+    fn test_generate_load_is_reproducible() {
+        let a = generate_load(7, 100, 10, 10, 10);
+        let b = generate_load(7, 100, 10, 10, 10);
+        assert_eq!(a, b);
+
+        let c = generate_load(8, 100, 10, 10, 10);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::domain::bed::{Bed, BedState, BlockReason};
+use crate::domain::error::ManifestError;
+use crate::domain::patient::{Gender, Patient};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What a manifest line says about a bed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ManifestEntry {
+    Occupied(Patient),
+    Vacant,
+    Blocked,
+    Maintenance,
+    Reserved {
+        clinical_record: u32,
+        until: Option<SystemTime>,
+    },
+}
+
+/// Parses a manifest into (line number, bed number, entry), skipping blank lines and
+/// `#` comments (a `#` inside a quoted name is part of the name). Each line looks like:
+/// - `201: 10001 "Maria Garcia" 30 F vip infected` (flags are optional, gender is M/F/X;
+///   `\"`, `\\` and `\n` in a name stand for a quote, a backslash and a line break)
+/// - `205: BLOCKED`, `206: MAINTENANCE` or `207: VACANT`
+/// - `208: RESERVED 10002` or `208: RESERVED 10002 until 1700000000` (seconds since the
+///   Unix epoch)
+pub(crate) fn parse_manifest(
+    text: &str,
+) -> Result<Vec<(usize, u32, ManifestEntry)>, ManifestError> {
+    let mut entries = Vec::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let error = |reason: String| ManifestError { line, reason };
+
+        let content = strip_comment(raw).trim();
+        if content.is_empty() {
+            continue;
+        }
+
+        let (bed, rest) = content
+            .split_once(':')
+            .ok_or_else(|| error("expected `<bed>: ...`".to_string()))?;
+        let bed_number: u32 = bed
+            .trim()
+            .parse()
+            .map_err(|_| error(format!("invalid bed number `{}`", bed.trim())))?;
+
+        let entry = match rest.trim() {
+            "VACANT" => ManifestEntry::Vacant,
+            "BLOCKED" => ManifestEntry::Blocked,
+            "MAINTENANCE" => ManifestEntry::Maintenance,
+            reserved if reserved.starts_with("RESERVED") => {
+                parse_reservation(&reserved["RESERVED".len()..]).map_err(error)?
+            }
+            patient => ManifestEntry::Occupied(parse_patient(patient).map_err(error)?),
+        };
+        entries.push((line, bed_number, entry));
+    }
+
+    Ok(entries)
+}
+
+/// Parses the rest of a reservation line: `10002` or `10002 until 1700000000`
+fn parse_reservation(text: &str) -> Result<ManifestEntry, String> {
+    let mut fields = text.split_whitespace();
+    let crn = fields.next().ok_or("missing clinical record number")?;
+    let clinical_record: u32 = crn
+        .parse()
+        .map_err(|_| format!("invalid clinical record number `{}`", crn))?;
+
+    let until = match (fields.next(), fields.next()) {
+        (None, _) => None,
+        (Some("until"), Some(secs)) => {
+            let secs: u64 = secs
+                .parse()
+                .map_err(|_| format!("invalid expiry `{}`", secs))?;
+            Some(UNIX_EPOCH + Duration::from_secs(secs))
+        }
+        _ => return Err("expected `RESERVED <crn> [until <seconds>]`".to_string()),
+    };
+    if let Some(extra) = fields.next() {
+        return Err(format!("unexpected `{}` after the reservation", extra));
+    }
+
+    Ok(ManifestEntry::Reserved {
+        clinical_record,
+        until,
+    })
+}
+
+/// The manifest line for a bed (without the bed number), or None if there is nothing to
+/// say: vacant beds and blocks on behalf of a patient, which admitting them recreates.
+/// Expiries are written in whole seconds.
+pub(crate) fn manifest_line(bed: &Bed) -> Option<String> {
+    match &bed.state {
+        BedState::Occupied(p) => {
+            let mut line = format!(
+                "{} \"{}\" {} {}",
+                p.clinical_record_number,
+                escape_name(&p.name),
+                p.age,
+                p.gender.code()
+            );
+            for (flag, on) in [
+                ("vip", p.is_vip),
+                ("infected", p.is_infected),
+                ("isolation", p.requires_isolation),
+            ] {
+                if on {
+                    line.push(' ');
+                    line.push_str(flag);
+                }
+            }
+            Some(line)
+        }
+        BedState::Vacant => None,
+        BedState::Blocked { reasons } => reasons
+            .contains(&BlockReason::Other)
+            .then(|| "BLOCKED".to_string()),
+        BedState::Maintenance => Some("MAINTENANCE".to_string()),
+        BedState::Reserved {
+            clinical_record,
+            until,
+        } => Some(match until {
+            Some(until) => format!(
+                "RESERVED {} until {}",
+                clinical_record,
+                until
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            ),
+            None => format!("RESERVED {}", clinical_record),
+        }),
+    }
+}
+
+/// A patient name as written between the quotes of a manifest line
+fn escape_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reads a quoted name that starts right after its opening quote (undoing `escape_name`)
+/// and returns it with the rest of the line after the closing quote
+fn unescape_name(text: &str) -> Result<(String, &str), String> {
+    let mut name = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((name, &text[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '"')) => name.push('"'),
+                Some((_, '\\')) => name.push('\\'),
+                Some((_, 'n')) => name.push('\n'),
+                Some((_, other)) => return Err(format!("invalid escape `\\{}` in name", other)),
+                None => break,
+            },
+            c => name.push(c),
+        }
+    }
+    Err("unterminated patient name".to_string())
+}
+
+/// The line up to its first `#` outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses `10001 "Maria Garcia" 30 F vip`
+fn parse_patient(text: &str) -> Result<Patient, String> {
+    let (crn, rest) = text
+        .split_once('"')
+        .ok_or("expected a quoted patient name")?;
+    let (name, rest) = unescape_name(rest)?;
+
+    let crn: u32 = crn
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid clinical record number `{}`", crn.trim()))?;
+
+    let mut fields = rest.split_whitespace();
+    let age = fields.next().ok_or("missing age")?;
+    let age: u8 = age.parse().map_err(|_| format!("invalid age `{}`", age))?;
+    let gender = match fields.next().ok_or("missing gender")? {
+        "M" => Gender::Male,
+        "F" => Gender::Female,
+        "X" => Gender::Other,
+        other => return Err(format!("invalid gender `{}` (expected M, F or X)", other)),
+    };
+
+    let mut patient =
+        Patient::try_new(crn, name, age, gender, false, false).map_err(|e| e.to_string())?;
+    for flag in fields {
+        match flag {
+            "vip" => patient.is_vip = true,
+            "infected" => patient.is_infected = true,
+            "isolation" => patient.requires_isolation = true,
+            other => return Err(format!("unknown flag `{}`", other)),
+        }
+    }
+
+    Ok(patient)
+}
@@ -1,4 +1,21 @@
+pub mod audit;
 pub mod bed;
+mod bed_map;
+pub mod clock;
+pub mod command;
 pub mod constants;
+pub mod error;
 pub mod hospital;
+pub mod invariant;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod load;
+mod manifest;
+pub mod observer;
 pub mod patient;
+pub mod relocation;
+pub mod room;
+pub mod rules;
+pub mod snapshot;
+pub mod summary;
+pub mod sync;
+pub mod warning;
@@ -0,0 +1,19 @@
+/// Hooks called by `Hospital` after a change has been committed (e.g. to refresh a dashboard).
+/// Every method does nothing by default, so observers only implement what they care about.
+/// Observers must be thread-safe so a hospital can be shared (see `SyncHospital`).
+pub trait HospitalObserver: Send + Sync {
+    /// A patient was admitted to `bed`
+    fn on_admit(&mut self, _clinical_record: u32, _bed: u32) {}
+
+    /// A patient left `bed` after being discharged
+    fn on_discharge(&mut self, _clinical_record: u32, _bed: u32) {}
+
+    /// A patient was moved (or switched) from one bed to another
+    fn on_move(&mut self, _clinical_record: u32, _from: u32, _to: u32) {}
+
+    /// `bed` was blocked on behalf of a VIP/infectious patient
+    fn on_block(&mut self, _bed: u32, _clinical_record: u32) {}
+
+    /// `bed` is no longer blocked on behalf of the patient
+    fn on_unblock(&mut self, _bed: u32, _clinical_record: u32) {}
+}
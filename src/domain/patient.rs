@@ -1,8 +1,37 @@
+use crate::domain::bed::BedCapability;
+use crate::domain::constants::MAX_PATIENT_AGE;
+use crate::domain::error::PatientError;
+use crate::domain::rules::{compatible_roommates, AdmissionRules};
+use std::fmt;
+
 /// Patient gender
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Gender {
     Male,
     Female,
+    /// Neither male nor female (shares rooms only with other `Other` patients)
+    Other,
+}
+
+impl Gender {
+    /// One-letter code used in listings and exports (M, F or X)
+    pub fn code(&self) -> char {
+        match self {
+            Gender::Male => 'M',
+            Gender::Female => 'F',
+            Gender::Other => 'X',
+        }
+    }
+}
+
+/// How urgently a patient needs a bed (higher goes first when beds are short)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Acuity {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
 }
 
 /// Structure that represents a patient
@@ -20,6 +49,20 @@ pub struct Patient {
     pub is_infected: bool,
     /// Whether the patient is a VIP
     pub is_vip: bool,
+    /// Whether the patient must be isolated (e.g. immunocompromised) even if not infectious.
+    /// They get a single room when possible, otherwise the rest of their room is blocked.
+    pub requires_isolation: bool,
+    /// Equipment their bed must have (e.g. telemetry); only beds with all of it are offered
+    pub requirements: Vec<BedCapability>,
+    /// Priority for a bed (Medium unless set)
+    pub acuity: Acuity,
+    /// Free-form clinical tags (e.g. "fall-risk", "NPO"), managed with `Hospital::add_tag`
+    pub tags: Vec<String>,
+}
+
+/// Does the clinical record number have 5 digits?
+pub(crate) fn is_valid_clinical_record(clinical_record_number: u32) -> bool {
+    (10000..=99999).contains(&clinical_record_number)
 }
 
 impl Patient {
@@ -33,7 +76,7 @@ impl Patient {
         is_vip: bool,
     ) -> Self {
         // Validate that the clinical record number has 5 digits
-        if !(10000..=99999).contains(&clinical_record_number) {
+        if !is_valid_clinical_record(clinical_record_number) {
             panic!("The clinical record number must have 5 digits");
         }
 
@@ -44,8 +87,120 @@ impl Patient {
             gender,
             is_infected,
             is_vip,
+            requires_isolation: false,
+            requirements: Vec::new(),
+            acuity: Acuity::default(),
+            tags: Vec::new(),
         }
     }
+
+    /// Like `new`, but returns an error instead of panicking, and also rejects ages above
+    /// `MAX_PATIENT_AGE` (newborns, age 0, are valid)
+    pub fn try_new(
+        clinical_record_number: u32,
+        name: String,
+        age: u8,
+        gender: Gender,
+        is_infected: bool,
+        is_vip: bool,
+    ) -> Result<Self, PatientError> {
+        if !is_valid_clinical_record(clinical_record_number) {
+            return Err(PatientError::InvalidClinicalRecord(clinical_record_number));
+        }
+        if age > MAX_PATIENT_AGE {
+            return Err(PatientError::InvalidAge(age));
+        }
+
+        Ok(Patient::new(
+            clinical_record_number,
+            name,
+            age,
+            gender,
+            is_infected,
+            is_vip,
+        ))
+    }
+
+    /// Can the two patients share a room under `rules`? Same gender, same age group, and
+    /// neither needs the room to themselves (infectious, isolation, or VIP when VIPs block
+    /// their room). Only the patients matter: no bed, unit or family room is considered.
+    pub fn can_room_with(&self, other: &Patient, rules: &AdmissionRules) -> bool {
+        compatible_roommates(self, other, rules).is_ok()
+    }
+}
+
+/// Shorthand constructors for tests and benchmarks (regular patients unless the name says
+/// otherwise)
+#[cfg(any(test, feature = "test-utils"))]
+impl Patient {
+    /// A regular male patient
+    pub fn adult_male(clinical_record_number: u32, name: &str, age: u8) -> Self {
+        Patient::new(
+            clinical_record_number,
+            name.into(),
+            age,
+            Gender::Male,
+            false,
+            false,
+        )
+    }
+
+    /// A regular female patient
+    pub fn adult_female(clinical_record_number: u32, name: &str, age: u8) -> Self {
+        Patient::new(
+            clinical_record_number,
+            name.into(),
+            age,
+            Gender::Female,
+            false,
+            false,
+        )
+    }
+
+    /// A VIP patient (not infectious)
+    pub fn vip(clinical_record_number: u32, name: &str, age: u8, gender: Gender) -> Self {
+        Patient::new(
+            clinical_record_number,
+            name.into(),
+            age,
+            gender,
+            false,
+            true,
+        )
+    }
+
+    /// An infectious patient (not VIP)
+    pub fn infected(clinical_record_number: u32, name: &str, age: u8, gender: Gender) -> Self {
+        Patient::new(
+            clinical_record_number,
+            name.into(),
+            age,
+            gender,
+            true,
+            false,
+        )
+    }
+}
+
+impl fmt::Display for Patient {
+    /// e.g. `Maria Garcia (#10001, 30F) [INFECTIOUS] [VIP]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (#{}, {}{})",
+            self.name,
+            self.clinical_record_number,
+            self.age,
+            self.gender.code()
+        )?;
+        if self.is_infected {
+            write!(f, " [INFECTIOUS]")?;
+        }
+        if self.is_vip {
+            write!(f, " [VIP]")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +226,89 @@ mod tests {
         assert!(!patient.is_vip);
     }
 
+    #[test]
+    fn test_try_new_validates_age() {
+        let newborn = Patient::try_new(10001, "Baby".into(), 0, Gender::Female, false, false);
+        assert_eq!(newborn.unwrap().age, 0);
+        assert!(Patient::try_new(10002, "Old".into(), 130, Gender::Male, false, false).is_ok());
+        assert_eq!(
+            Patient::try_new(10003, "Typo".into(), 200, Gender::Male, false, false),
+            Err(PatientError::InvalidAge(200))
+        );
+        assert_eq!(
+            Patient::try_new(123, "Short".into(), 30, Gender::Male, false, false),
+            Err(PatientError::InvalidClinicalRecord(123))
+        );
+    }
+
+    #[test]
+    fn test_display_patient() {
+        let maria = Patient::new(
+            10001,
+            "Maria Garcia".into(),
+            30,
+            Gender::Female,
+            false,
+            false,
+        );
+        assert_eq!(maria.to_string(), "Maria Garcia (#10001, 30F)");
+
+        let p = Patient::new(10002, "John Smith".into(), 45, Gender::Male, true, true);
+        assert_eq!(p.to_string(), "John Smith (#10002, 45M) [INFECTIOUS] [VIP]");
+    }
+
+    #[test]
+    fn test_shorthand_constructors() {
+        let man = Patient::adult_male(10001, "Man", 40);
+        assert_eq!(
+            (man.gender, man.age, man.name.as_str()),
+            (Gender::Male, 40, "Man")
+        );
+        assert!(!man.is_vip && !man.is_infected);
+
+        let woman = Patient::adult_female(10002, "Woman", 35);
+        assert_eq!(woman.gender, Gender::Female);
+        assert!(!woman.is_vip && !woman.is_infected);
+
+        let vip = Patient::vip(10003, "VIP", 50, Gender::Other);
+        assert!(vip.is_vip && !vip.is_infected);
+        assert_eq!(vip.gender, Gender::Other);
+
+        let infected = Patient::infected(10004, "Infected", 60, Gender::Male);
+        assert!(infected.is_infected && !infected.is_vip);
+    }
+
+    #[test]
+    fn test_can_room_with() {
+        let rules = AdmissionRules::default();
+        let man = Patient::adult_male(10001, "Man", 40);
+        let other_man = Patient::adult_male(10002, "Other man", 70);
+        let woman = Patient::adult_female(10003, "Woman", 40);
+        let teen = Patient::new(10004, "Teen".into(), 14, Gender::Male, false, false);
+        let other_teen = Patient::new(10005, "Other teen".into(), 15, Gender::Male, false, false);
+        let infected = Patient::infected(10006, "Infected", 40, Gender::Male);
+        let vip = Patient::vip(10007, "VIP", 40, Gender::Male);
+        let mut isolated = Patient::adult_male(10008, "Isolated", 40);
+        isolated.requires_isolation = true;
+
+        assert!(man.can_room_with(&other_man, &rules));
+        assert!(teen.can_room_with(&other_teen, &rules));
+        assert!(!man.can_room_with(&woman, &rules));
+        assert!(!man.can_room_with(&teen, &rules));
+        assert!(!teen.can_room_with(&man, &rules));
+        for needs_own_room in [&infected, &vip, &isolated] {
+            assert!(!man.can_room_with(needs_own_room, &rules));
+            assert!(!needs_own_room.can_room_with(&man, &rules));
+        }
+
+        let relaxed = AdmissionRules {
+            vip_blocks_adjacent: false,
+            ..rules
+        };
+        assert!(man.can_room_with(&vip, &relaxed));
+        assert!(!man.can_room_with(&infected, &relaxed));
+    }
+
     #[test]
     #[should_panic(expected = "The clinical record number must have 5 digits")]
     fn test_invalid_record_number() {
@@ -0,0 +1,57 @@
+use crate::domain::hospital::Hospital;
+use crate::domain::patient::Patient;
+
+/// Decides where a roommate goes when they have to leave a room (a patient next to them
+/// became VIP or infectious). `exclude` is the bed of that patient: its whole room is off
+/// limits. Strategies must be thread-safe so a hospital can be shared (see `SyncHospital`).
+pub trait RelocationStrategy: Send + Sync {
+    fn choose(&self, hospital: &Hospital, patient: &Patient, exclude: u32) -> Option<u32>;
+}
+
+/// What happens when a roommate who has to leave a room can't be placed anywhere
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RelocationFailurePolicy {
+    /// The whole operation fails with `NoBedForRoommate` and nothing changes (the default)
+    #[default]
+    Abort,
+    /// The roommate is discharged to the waitlist and the operation goes ahead
+    WaitlistRoommate,
+}
+
+/// Valid beds for the patient outside the room of `exclude`, sorted
+fn candidates(hospital: &Hospital, patient: &Patient, exclude: u32) -> Vec<u32> {
+    let room = hospital.room_members(exclude);
+    hospital
+        .get_available_beds_for_patient(patient)
+        .into_iter()
+        .filter(|b| !room.contains(b))
+        .collect()
+}
+
+/// The lowest valid bed anywhere in the hospital
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowestBedStrategy;
+
+impl RelocationStrategy for LowestBedStrategy {
+    fn choose(&self, hospital: &Hospital, patient: &Patient, exclude: u32) -> Option<u32> {
+        candidates(hospital, patient, exclude).first().copied()
+    }
+}
+
+/// The lowest valid bed in the same unit, falling back to the lowest bed in any other unit
+/// (the default: staff and equipment stay with the patient)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SameUnitStrategy;
+
+impl RelocationStrategy for SameUnitStrategy {
+    fn choose(&self, hospital: &Hospital, patient: &Patient, exclude: u32) -> Option<u32> {
+        let unit = hospital.unit_of(exclude);
+        let candidates = candidates(hospital, patient, exclude);
+
+        candidates
+            .iter()
+            .find(|&&b| hospital.unit_of(b) == unit)
+            .or(candidates.first())
+            .copied()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::bed::{Bed, BedState};
+use crate::domain::patient::{Gender, Patient};
+
+/// A room: the beds that share it and their current state, built by `Hospital::room`
+/// and `Hospital::rooms_in_unit` from the hospital's layout
+#[derive(Debug, Clone)]
+pub struct Room<'a> {
+    /// Member beds, sorted by bed number
+    beds: Vec<(u32, &'a Bed)>,
+}
+
+impl<'a> Room<'a> {
+    /// Builds a room from its member beds (sorted by bed number)
+    pub(crate) fn new(beds: Vec<(u32, &'a Bed)>) -> Self {
+        Room { beds }
+    }
+
+    /// Bed numbers in the room, sorted
+    pub fn bed_numbers(&self) -> Vec<u32> {
+        self.beds.iter().map(|&(n, _)| n).collect()
+    }
+
+    /// First bed of the room (identifies it)
+    pub fn first_bed(&self) -> u32 {
+        self.beds[0].0
+    }
+
+    /// Number of beds in the room
+    pub fn size(&self) -> usize {
+        self.beds.len()
+    }
+
+    /// Is every bed of the room occupied? (a blocked or maintenance bed means it isn't)
+    pub fn is_full(&self) -> bool {
+        self.beds
+            .iter()
+            .all(|(_, bed)| matches!(bed.state, BedState::Occupied(_)))
+    }
+
+    /// Is nobody in the room?
+    pub fn is_empty(&self) -> bool {
+        self.occupants().is_empty()
+    }
+
+    /// Occupied beds and their patients, sorted by bed number
+    pub fn occupants(&self) -> Vec<(u32, &'a Patient)> {
+        self.beds
+            .iter()
+            .filter_map(|&(n, bed)| match &bed.state {
+                BedState::Occupied(p) => Some((n, p)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Vacant beds of the room, sorted
+    pub fn vacant_beds(&self) -> Vec<u32> {
+        self.beds
+            .iter()
+            .filter(|(_, bed)| bed.is_available())
+            .map(|&(n, _)| n)
+            .collect()
+    }
+
+    /// The gender most occupants have. None if the room is empty or there is a tie.
+    pub fn dominant_gender(&self) -> Option<Gender> {
+        let occupants = self.occupants();
+        let count = |g: Gender| occupants.iter().filter(|(_, p)| p.gender == g).count();
+
+        let mut counts = [Gender::Male, Gender::Female, Gender::Other].map(|g| (g, count(g)));
+        counts.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        match counts {
+            [(_, 0), ..] => None,
+            [(g, first), (_, second), _] if first > second => Some(g),
+            _ => None,
+        }
+    }
+
+    /// Is any occupant infectious or VIP?
+    pub fn has_infected_or_vip(&self) -> bool {
+        self.occupants()
+            .iter()
+            .any(|(_, p)| p.is_infected || p.is_vip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occupied(patient: Patient) -> Bed {
+        Bed {
+            state: BedState::Occupied(patient),
+            capabilities: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_full_in_two_bed_room() {
+        let vacant = Bed::new(101);
+        let p1 = occupied(Patient::adult_male(10001, "P1", 40));
+        let p2 = occupied(Patient::adult_male(10002, "P2", 50));
+
+        assert!(!Room::new(vec![(101, &vacant), (102, &vacant)]).is_full());
+        assert!(!Room::new(vec![(101, &p1), (102, &vacant)]).is_full());
+        assert!(Room::new(vec![(101, &p1), (102, &p2)]).is_full());
+    }
+
+    #[test]
+    fn test_has_infected_or_vip() {
+        let vacant = Bed::new(102);
+        let normal = occupied(Patient::adult_female(10001, "Normal", 40));
+        let infected = occupied(Patient::infected(10002, "Infected", 40, Gender::Female));
+        let vip = occupied(Patient::vip(10003, "VIP", 60, Gender::Male));
+
+        assert!(!Room::new(vec![(101, &vacant), (102, &vacant)]).has_infected_or_vip());
+        assert!(!Room::new(vec![(101, &normal), (102, &vacant)]).has_infected_or_vip());
+        assert!(Room::new(vec![(101, &normal), (102, &infected)]).has_infected_or_vip());
+        assert!(Room::new(vec![(101, &vip), (102, &vacant)]).has_infected_or_vip());
+    }
+
+    #[test]
+    fn test_dominant_gender() {
+        let vacant = Bed::new(101);
+        let m = occupied(Patient::adult_male(10001, "M", 40));
+        let f = occupied(Patient::adult_female(10002, "F", 40));
+        let m2 = occupied(Patient::adult_male(10003, "M2", 40));
+
+        assert_eq!(Room::new(vec![(101, &vacant)]).dominant_gender(), None);
+        assert_eq!(
+            Room::new(vec![(101, &m), (102, &f)]).dominant_gender(),
+            None
+        );
+        assert_eq!(
+            Room::new(vec![(101, &m), (102, &f), (103, &m2)]).dominant_gender(),
+            Some(Gender::Male)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::domain::constants::{MINOR_SHARED_ROOM_MAX_AGE, PEDIATRIC_MAX_AGE, PEDIATRIC_UNIT};
+use crate::domain::error::HospitalError;
+use crate::domain::patient::Patient;
+
+/// Admission thresholds a hospital can tune (defaults come from the shared constants)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdmissionRules {
+    /// Patients younger than this must be admitted to the pediatric unit
+    pub pediatric_max_age: u8,
+    /// Patients younger than this can only share a room with each other
+    pub minor_shared_room_max_age: u8,
+    /// Whether a VIP patient blocks the rest of their room (infection always does)
+    pub vip_blocks_adjacent: bool,
+}
+
+impl AdmissionRules {
+    /// Does the patient have to be in the pediatric unit?
+    pub fn is_pediatric(&self, patient: &Patient) -> bool {
+        self.is_pediatric_age(patient.age)
+    }
+
+    /// Does a patient of this age have to be in the pediatric unit?
+    pub fn is_pediatric_age(&self, age: u8) -> bool {
+        age < self.pediatric_max_age
+    }
+
+    /// Is the patient in the minors' room-sharing group?
+    pub fn is_minor(&self, patient: &Patient) -> bool {
+        self.is_minor_age(patient.age)
+    }
+
+    /// Is a patient of this age in the minors' room-sharing group?
+    pub fn is_minor_age(&self, age: u8) -> bool {
+        age < self.minor_shared_room_max_age
+    }
+
+    /// Is this patient allowed in `unit`?
+    pub fn allows_unit(&self, patient: &Patient, unit: u16) -> bool {
+        self.allows_unit_for_age(patient.age, unit)
+    }
+
+    /// Is a patient of this age allowed in `unit`?
+    pub fn allows_unit_for_age(&self, age: u8, unit: u16) -> bool {
+        !self.is_pediatric_age(age) || unit == PEDIATRIC_UNIT
+    }
+
+    /// Does the patient need their room to themselves (infectious, isolation, or VIP when
+    /// VIPs block their room)?
+    pub fn needs_own_room(&self, patient: &Patient) -> bool {
+        patient.is_infected
+            || patient.requires_isolation
+            || (patient.is_vip && self.vip_blocks_adjacent)
+    }
+}
+
+/// Checks whether patient `a` can be put in the same room as patient `b`.
+/// Errors, in order: different gender, different age group, `b` needs the room to
+/// themselves (`RoommateRequiresIsolation`), `a` needs it (`AdjacentBedNotFree`).
+pub fn compatible_roommates(
+    a: &Patient,
+    b: &Patient,
+    rules: &AdmissionRules,
+) -> Result<(), HospitalError> {
+    if a.gender != b.gender {
+        return Err(HospitalError::GenderMismatch);
+    }
+    compatible_roommates_any_gender(a, b, rules)
+}
+
+/// `compatible_roommates` without the gender rule (for family rooms)
+pub(crate) fn compatible_roommates_any_gender(
+    a: &Patient,
+    b: &Patient,
+    rules: &AdmissionRules,
+) -> Result<(), HospitalError> {
+    if rules.is_minor(a) != rules.is_minor(b) {
+        return Err(HospitalError::AgeGroupMismatch);
+    }
+    if rules.needs_own_room(b) {
+        return Err(HospitalError::RoommateRequiresIsolation);
+    }
+    if rules.needs_own_room(a) {
+        return Err(HospitalError::AdjacentBedNotFree);
+    }
+    Ok(())
+}
+
+impl Default for AdmissionRules {
+    fn default() -> Self {
+        AdmissionRules {
+            pediatric_max_age: PEDIATRIC_MAX_AGE,
+            minor_shared_room_max_age: MINOR_SHARED_ROOM_MAX_AGE,
+            vip_blocks_adjacent: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::patient::Gender;
+
+    #[test]
+    fn test_default_thresholds() {
+        let rules = AdmissionRules::default();
+        let child = Patient::new(10001, "Child".into(), 12, Gender::Male, false, false);
+        let teen = Patient::new(10002, "Teen".into(), 15, Gender::Male, false, false);
+        let adult = Patient::new(10003, "Adult".into(), 16, Gender::Male, false, false);
+
+        assert!(rules.is_pediatric(&child));
+        assert!(!rules.is_pediatric(&teen));
+        assert!(rules.is_minor(&teen));
+        assert!(!rules.is_minor(&adult));
+
+        assert!(rules.allows_unit(&child, 5));
+        assert!(!rules.allows_unit(&child, 1));
+        assert!(rules.allows_unit(&adult, 5));
+    }
+
+    #[test]
+    fn test_compatible_roommates() {
+        let rules = AdmissionRules::default();
+        let adult = Patient::new(10001, "Adult".into(), 40, Gender::Male, false, false);
+        let other = Patient::new(10002, "Other".into(), 50, Gender::Male, false, false);
+        let woman = Patient::new(10003, "Woman".into(), 40, Gender::Female, false, false);
+        let teen = Patient::new(10004, "Teen".into(), 15, Gender::Male, false, false);
+        let infected = Patient::new(10005, "Infected".into(), 40, Gender::Male, true, false);
+        let vip = Patient::new(10006, "VIP".into(), 40, Gender::Male, false, true);
+        let mut isolated = other.clone();
+        isolated.requires_isolation = true;
+
+        assert_eq!(compatible_roommates(&adult, &other, &rules), Ok(()));
+        assert_eq!(
+            compatible_roommates(&adult, &woman, &rules),
+            Err(HospitalError::GenderMismatch)
+        );
+        assert_eq!(
+            compatible_roommates(&adult, &teen, &rules),
+            Err(HospitalError::AgeGroupMismatch)
+        );
+        for roommate in [&infected, &vip, &isolated] {
+            assert_eq!(
+                compatible_roommates(&adult, roommate, &rules),
+                Err(HospitalError::RoommateRequiresIsolation)
+            );
+            assert_eq!(
+                compatible_roommates(roommate, &adult, &rules),
+                Err(HospitalError::AdjacentBedNotFree)
+            );
+        }
+
+        // VIPs can share when the policy says they don't block their room
+        let relaxed = AdmissionRules {
+            vip_blocks_adjacent: false,
+            ..rules
+        };
+        assert_eq!(compatible_roommates(&adult, &vip, &relaxed), Ok(()));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::domain::bed::BedState;
+use std::collections::BTreeMap;
+
+/// Point-in-time copy of every bed state, taken with `Hospital::snapshot`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HospitalSnapshot {
+    pub(crate) beds: BTreeMap<u32, BedState>,
+}
+
+impl HospitalSnapshot {
+    /// State of a bed when the snapshot was taken (None if it doesn't exist)
+    pub fn bed_state(&self, bed_number: u32) -> Option<&BedState> {
+        self.beds.get(&bed_number)
+    }
+}
+
+/// A bed whose state differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BedChange {
+    pub bed: u32,
+    /// None if the bed didn't exist in the earlier snapshot
+    pub before: Option<BedState>,
+    /// None if the bed doesn't exist in the later snapshot
+    pub after: Option<BedState>,
+}
+
+/// Lists every bed whose state changed from `before` to `after`, sorted by bed number
+pub fn diff_snapshots(before: &HospitalSnapshot, after: &HospitalSnapshot) -> Vec<BedChange> {
+    let mut bed_numbers: Vec<u32> = before
+        .beds
+        .keys()
+        .chain(after.beds.keys())
+        .copied()
+        .collect();
+    bed_numbers.sort_unstable();
+    bed_numbers.dedup();
+
+    bed_numbers
+        .into_iter()
+        .filter_map(|bed| {
+            let old = before.beds.get(&bed);
+            let new = after.beds.get(&bed);
+            (old != new).then(|| BedChange {
+                bed,
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
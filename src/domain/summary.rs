@@ -0,0 +1,47 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Compact occupancy counts for dashboards and status endpoints, built by
+/// `Hospital::summary` (serializable with the `serde` feature)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct OccupancySummary {
+    pub total: usize,
+    pub occupied: usize,
+    pub vacant: usize,
+    pub blocked: usize,
+    pub maintenance: usize,
+    pub reserved: usize,
+    /// One entry per unit, sorted by unit
+    pub units: Vec<UnitOccupancy>,
+    /// Infectious patients admitted
+    pub infected: usize,
+    /// VIP patients admitted
+    pub vip: usize,
+}
+
+/// Occupancy counts of a single unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct UnitOccupancy {
+    pub unit: u16,
+    pub total: usize,
+    pub occupied: usize,
+    pub vacant: usize,
+    pub blocked: usize,
+    pub maintenance: usize,
+    pub reserved: usize,
+}
+
+impl UnitOccupancy {
+    /// Fraction of the usable beds (occupied or vacant) that are occupied, 0.0..=1.0.
+    /// Blocked, maintenance and reserved beds can't take anyone, so they don't count; a unit
+    /// with no usable bed is full (1.0).
+    pub fn occupancy_rate(&self) -> f64 {
+        let usable = self.occupied + self.vacant;
+        if usable == 0 {
+            return 1.0;
+        }
+        self.occupied as f64 / usable as f64
+    }
+}
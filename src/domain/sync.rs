@@ -0,0 +1,155 @@
+use crate::domain::error::HospitalError;
+use crate::domain::hospital::{DischargeOutcome, Hospital};
+use crate::domain::patient::Patient;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Thread-safe `Hospital` that can be shared through an `Arc` (e.g. by a web server).
+/// Queries take a read lock and operations a write lock, so every operation is still atomic.
+pub struct SyncHospital {
+    inner: RwLock<Hospital>,
+}
+
+impl SyncHospital {
+    /// Wraps an existing hospital
+    pub fn new(hospital: Hospital) -> Self {
+        SyncHospital {
+            inner: RwLock::new(hospital),
+        }
+    }
+
+    /// Unwraps the hospital (e.g. once every thread is done)
+    pub fn into_inner(self) -> Hospital {
+        self.inner.into_inner().expect("hospital lock poisoned")
+    }
+
+    /// Runs a query with a read lock (for anything without a wrapper below)
+    pub fn read<T>(&self, f: impl FnOnce(&Hospital) -> T) -> T {
+        f(&self.read_lock())
+    }
+
+    /// Runs several changes under one write lock, so no other thread sees them half done
+    pub fn write<T>(&self, f: impl FnOnce(&mut Hospital) -> T) -> T {
+        f(&mut self.write_lock())
+    }
+
+    fn read_lock(&self) -> RwLockReadGuard<'_, Hospital> {
+        self.inner.read().expect("hospital lock poisoned")
+    }
+
+    fn write_lock(&self) -> RwLockWriteGuard<'_, Hospital> {
+        self.inner.write().expect("hospital lock poisoned")
+    }
+
+    // ----------------- Operations -----------------
+
+    /// See `Hospital::admit_patient`
+    pub fn admit_patient(&self, patient: &Patient, bed_number: u32) -> Result<(), HospitalError> {
+        self.write_lock().admit_patient(patient, bed_number)
+    }
+
+    /// See `Hospital::move_patient`
+    pub fn move_patient(&self, clinical_record: u32, new_bed: u32) -> Result<(), HospitalError> {
+        self.write_lock().move_patient(clinical_record, new_bed)
+    }
+
+    /// See `Hospital::switch_patients`
+    pub fn switch_patients(
+        &self,
+        clinical_record1: u32,
+        clinical_record2: u32,
+    ) -> Result<(), HospitalError> {
+        self.write_lock()
+            .switch_patients(clinical_record1, clinical_record2)
+    }
+
+    /// See `Hospital::set_patient_vip`
+    pub fn set_patient_vip(
+        &self,
+        clinical_record: u32,
+        is_vip: bool,
+    ) -> Result<Option<u32>, HospitalError> {
+        self.write_lock().set_patient_vip(clinical_record, is_vip)
+    }
+
+    /// See `Hospital::mark_patient_as_infected`
+    pub fn mark_patient_as_infected(
+        &self,
+        clinical_record: u32,
+    ) -> Result<Option<u32>, HospitalError> {
+        self.write_lock().mark_patient_as_infected(clinical_record)
+    }
+
+    /// See `Hospital::unmark_patient_as_infected`
+    pub fn unmark_patient_as_infected(&self, clinical_record: u32) -> Result<(), HospitalError> {
+        self.write_lock()
+            .unmark_patient_as_infected(clinical_record)
+    }
+
+    /// See `Hospital::discharge_patient`
+    pub fn discharge_patient(
+        &self,
+        clinical_record: u32,
+    ) -> Result<DischargeOutcome, HospitalError> {
+        self.write_lock().discharge_patient(clinical_record)
+    }
+
+    // ----------------- Queries -----------------
+
+    /// See `Hospital::find_patient_info`
+    pub fn find_patient_info(&self, clinical_record: u32) -> Option<(u32, Patient)> {
+        self.read_lock().find_patient_info(clinical_record)
+    }
+
+    /// See `Hospital::get_available_beds_for_patient`
+    pub fn get_available_beds_for_patient(&self, patient: &Patient) -> Vec<u32> {
+        self.read_lock().get_available_beds_for_patient(patient)
+    }
+
+    /// See `Hospital::recommend_bed`
+    pub fn recommend_bed(&self, patient: &Patient) -> Option<u32> {
+        self.read_lock().recommend_bed(patient)
+    }
+
+    /// See `Hospital::count_beds_by_state`
+    pub fn count_beds_by_state(&self) -> (usize, usize, usize, usize) {
+        self.read_lock().count_beds_by_state()
+    }
+}
+
+impl From<Hospital> for SyncHospital {
+    fn from(hospital: Hospital) -> Self {
+        SyncHospital::new(hospital)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::constants::TOTAL_BEDS;
+    use crate::domain::patient::Gender;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_admissions() {
+        let hospital = Arc::new(SyncHospital::new(Hospital::new()));
+
+        let handles: Vec<_> = (0..8u32)
+            .map(|i| {
+                let hospital = Arc::clone(&hospital);
+                thread::spawn(move || {
+                    let crn = 10000 + i;
+                    let p = Patient::new(crn, "P".into(), 40, Gender::Male, false, false);
+                    hospital.admit_patient(&p, 101 + i * 2)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Ok(()));
+        }
+        assert_eq!(hospital.count_beds_by_state(), (8, TOTAL_BEDS - 8, 0, 0));
+        assert_eq!(hospital.find_patient_info(10007).unwrap().0, 115);
+        assert!(hospital.read(|h| h.validate_invariants().is_empty()));
+    }
+}
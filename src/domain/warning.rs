@@ -0,0 +1,9 @@
+/// A non-blocking advisory from `Hospital::admission_warnings`: the rules allow the
+/// admission, but staff may prefer another bed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdmissionWarning {
+    /// A child would lie next to an infectious/isolated patient in the neighbouring room
+    AdjacentToInfectious { bed: u32, clinical_record: u32 },
+    /// A child would lie next to a bed blocked for an infectious/isolated patient
+    AdjacentToIsolationBlock { bed: u32, clinical_record: u32 },
+}
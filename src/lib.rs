@@ -1 +1,29 @@
+//! Simplified hospital bed management: beds numbered UXX in units 1, 2, 4 and 5, two-bed
+//! rooms, and the admission rules (gender, age, VIP and infectious patients).
+//!
+//! The types most callers need are re-exported at the top level; everything else lives in
+//! `domain`.
+//!
+//! ```
+//! use bed_management::{BedState, Gender, Hospital, HospitalError, Patient};
+//!
+//! let mut hospital = Hospital::new();
+//! let vip = Patient::new(10001, "Maria Garcia".to_string(), 30, Gender::Female, false, true);
+//! hospital.admit_patient(&vip, 101).unwrap();
+//!
+//! assert!(matches!(hospital.bed(101).unwrap().state, BedState::Occupied(_)));
+//! assert!(hospital.bed(102).unwrap().is_blocked()); // VIPs get the room to themselves
+//!
+//! let other = Patient::new(10002, "Ana Ruiz".to_string(), 40, Gender::Female, false, false);
+//! assert_eq!(
+//!     hospital.admit_patient(&other, 102),
+//!     Err(HospitalError::BedNotAvailable(102))
+//! );
+//! ```
+
 pub mod domain;
+
+pub use domain::bed::{Bed, BedState, BlockReason};
+pub use domain::error::HospitalError;
+pub use domain::hospital::Hospital;
+pub use domain::patient::{Acuity, Gender, Patient};
@@ -1,8 +1,5 @@
-mod domain;
-
-use domain::constants::TOTAL_BEDS;
-use domain::hospital::Hospital;
-use domain::patient::{Gender, Patient};
+use bed_management::domain::constants::TOTAL_BEDS;
+use bed_management::{Gender, Hospital, Patient};
 
 fn main() {
     let mut hospital = Hospital::new();
@@ -55,35 +52,35 @@ fn main() {
     // --------------------------------
     println!(
         "Admit Maria to 201 -> {:?}",
-        hospital.admit_patient(maria.clone(), 201)
+        hospital.admit_patient(&maria, 201)
     );
 
     // Should fail - different gender cannot share room
     println!(
         "Admit John to 202 -> {:?}",
-        hospital.admit_patient(juan.clone(), 202)
+        hospital.admit_patient(&juan, 202)
     );
 
     // Should work - empty room
     println!(
         "Admit John to 205 -> {:?}",
-        hospital.admit_patient(juan.clone(), 205)
+        hospital.admit_patient(&juan, 205)
     );
 
     // Test rule: children under 13 must go to unit 5
     println!(
         "Admit Carlos to 101 -> {:?}",
-        hospital.admit_patient(carlos_nino.clone(), 101)
+        hospital.admit_patient(&carlos_nino, 101)
     );
     println!(
         "Admit Carlos to 501 -> {:?}",
-        hospital.admit_patient(carlos_nino.clone(), 501)
+        hospital.admit_patient(&carlos_nino, 501)
     );
 
     // Admit another adult female
     println!(
         "Admit Ana to 203 -> {:?}",
-        hospital.admit_patient(ana.clone(), 203)
+        hospital.admit_patient(&ana, 203)
     );
 
     // --------------------------------
@@ -105,7 +102,7 @@ fn main() {
     );
     println!(
         "Admit test patient to 206 -> {:?}",
-        hospital.admit_patient(paciente_prueba.clone(), 206)
+        hospital.admit_patient(&paciente_prueba, 206)
     );
 
     // --------------------------------
@@ -153,7 +150,7 @@ fn main() {
     // --------------------------------
     println!(
         "Admit Pedro to 209 -> {:?}",
-        hospital.admit_patient(pedro.clone(), 209)
+        hospital.admit_patient(&pedro, 209)
     );
     println!(
         "Switch John and Pedro -> {:?}",
@@ -180,10 +177,10 @@ fn main() {
     // --------------------------------
     // 9) Display bed statistics
     // --------------------------------
-    let (ocupadas, libres, bloqueadas) = hospital.count_beds_by_state();
+    let (ocupadas, libres, bloqueadas, mantenimiento) = hospital.count_beds_by_state();
     println!(
-        "Summary: {} occupied, {} vacant, {} blocked",
-        ocupadas, libres, bloqueadas
+        "Summary: {} occupied, {} vacant, {} blocked, {} in maintenance",
+        ocupadas, libres, bloqueadas, mantenimiento
     );
 
     // --------------------------------
@@ -12,10 +12,10 @@ fn test_complete_lifecycle() {
     let p4 = Patient::new(10004, "P4".to_string(), 28, Gender::Female, false, false);
 
     // Admit patients in different rooms
-    assert!(hospital.admit_patient(p1, 101).is_ok());
-    assert!(hospital.admit_patient(p2, 102).is_ok()); // Same room as p1
-    assert!(hospital.admit_patient(p3, 201).is_ok());
-    assert!(hospital.admit_patient(p4, 202).is_ok()); // Same room as p3
+    assert!(hospital.admit_patient(&p1, 101).is_ok());
+    assert!(hospital.admit_patient(&p2, 102).is_ok()); // Same room as p1
+    assert!(hospital.admit_patient(&p3, 201).is_ok());
+    assert!(hospital.admit_patient(&p4, 202).is_ok()); // Same room as p3
 
     // Mark p1 as infectious - should move p2 to another bed
     assert!(hospital.mark_patient_as_infected(10001).is_ok());
@@ -40,20 +40,20 @@ fn test_pediatric_unit() {
     // Should fail in units 1, 2, 4
 
     // Should fail in units 1, 2, 4
-    assert!(hospital.admit_patient(child.clone(), 101).is_err());
-    assert!(hospital.admit_patient(child.clone(), 201).is_err());
-    assert!(hospital.admit_patient(child.clone(), 401).is_err());
+    assert!(hospital.admit_patient(&child, 101).is_err());
+    assert!(hospital.admit_patient(&child, 201).is_err());
+    assert!(hospital.admit_patient(&child, 401).is_err());
 
     // Should succeed in unit 5
-    assert!(hospital.admit_patient(child, 501).is_ok());
+    assert!(hospital.admit_patient(&child, 501).is_ok());
 
     // Rule: Under-16 can only share with other under-16
     let teen = Patient::new(20002, "Teen".to_string(), 14, Gender::Female, false, false);
-    assert!(hospital.admit_patient(teen, 101).is_ok());
+    assert!(hospital.admit_patient(&teen, 101).is_ok());
 
     // Adult cannot share room with teen
     let adult = Patient::new(20003, "Adult".to_string(), 25, Gender::Female, false, false);
-    assert!(hospital.admit_patient(adult, 102).is_err());
+    assert!(hospital.admit_patient(&adult, 102).is_err());
 }
 
 #[test]
@@ -62,14 +62,14 @@ fn test_vip_and_infectious() {
 
     // Rule: VIP patients block the adjacent bed
     let vip = Patient::new(30001, "VIP".to_string(), 50, Gender::Male, false, true);
-    assert!(hospital.admit_patient(vip, 101).is_ok());
+    assert!(hospital.admit_patient(&vip, 101).is_ok());
 
     // Bed 102 should be blocked
     let normal = Patient::new(30002, "Normal".to_string(), 40, Gender::Male, false, false);
-    assert!(hospital.admit_patient(normal.clone(), 102).is_err());
+    assert!(hospital.admit_patient(&normal, 102).is_err());
 
     // But other beds are available
-    assert!(hospital.admit_patient(normal, 103).is_ok());
+    assert!(hospital.admit_patient(&normal, 103).is_ok());
 }
 
 #[test]
@@ -87,9 +87,9 @@ fn test_gender_mixing() {
         false,
     );
 
-    assert!(hospital.admit_patient(male, 201).is_ok());
+    assert!(hospital.admit_patient(&male, 201).is_ok());
     // Female cannot share room with male
-    assert!(hospital.admit_patient(female, 202).is_err());
+    assert!(hospital.admit_patient(&female, 202).is_err());
 }
 
 #[test]
@@ -99,7 +99,7 @@ fn test_move_patient_rules() {
     let p = Patient::new(50001, "P".to_string(), 30, Gender::Male, false, false);
 
     // Admit and move to valid bed
-    assert!(hospital.admit_patient(p, 101).is_ok());
+    assert!(hospital.admit_patient(&p, 101).is_ok());
     assert!(hospital.move_patient(50001, 201).is_ok());
 
     // Cannot move to non-existent bed
@@ -114,16 +114,16 @@ fn test_switch_patients_validation() {
     let m1 = Patient::new(60001, "M1".to_string(), 30, Gender::Male, false, false);
     let m2 = Patient::new(60002, "M2".to_string(), 35, Gender::Male, false, false);
 
-    hospital.admit_patient(m1, 101).unwrap();
-    hospital.admit_patient(m2, 201).unwrap();
+    hospital.admit_patient(&m1, 101).unwrap();
+    hospital.admit_patient(&m2, 201).unwrap();
     assert!(hospital.switch_patients(60001, 60002).is_ok());
 
     // Case 2: Child under 13 cannot leave unit 5
     let child = Patient::new(60003, "Child".to_string(), 10, Gender::Male, false, false);
     let adult = Patient::new(60004, "Adult".to_string(), 30, Gender::Male, false, false);
 
-    hospital.admit_patient(child, 501).unwrap();
-    hospital.admit_patient(adult, 401).unwrap();
+    hospital.admit_patient(&child, 501).unwrap();
+    hospital.admit_patient(&adult, 401).unwrap();
     // Switch would move child out of unit 5 - should fail
     assert!(hospital.switch_patients(60003, 60004).is_err());
 }
@@ -135,8 +135,8 @@ fn test_mark_infected_moves_roommate() {
     let p1 = Patient::new(70001, "P1".to_string(), 30, Gender::Male, false, false);
     let p2 = Patient::new(70002, "P2".to_string(), 35, Gender::Male, false, false);
 
-    hospital.admit_patient(p1, 101).unwrap();
-    hospital.admit_patient(p2, 102).unwrap(); // Roommate
+    hospital.admit_patient(&p1, 101).unwrap();
+    hospital.admit_patient(&p2, 102).unwrap(); // Roommate
 
     // When marking p1 as infected, p2 should be moved automatically
     assert!(hospital.mark_patient_as_infected(70001).is_ok());
@@ -161,11 +161,11 @@ fn test_all_rules_together() {
     ];
 
     // Admit patients following all rules
-    assert!(hospital.admit_patient(patients[0].clone(), 101).is_ok());
-    assert!(hospital.admit_patient(patients[1].clone(), 102).is_ok());
-    assert!(hospital.admit_patient(patients[2].clone(), 201).is_ok());
-    assert!(hospital.admit_patient(patients[3].clone(), 501).is_ok()); // Child in unit 5
-    assert!(hospital.admit_patient(patients[4].clone(), 401).is_ok()); // VIP blocks 402
+    assert!(hospital.admit_patient(&patients[0], 101).is_ok());
+    assert!(hospital.admit_patient(&patients[1], 102).is_ok());
+    assert!(hospital.admit_patient(&patients[2], 201).is_ok());
+    assert!(hospital.admit_patient(&patients[3], 501).is_ok()); // Child in unit 5
+    assert!(hospital.admit_patient(&patients[4], 401).is_ok()); // VIP blocks 402
 
     // Test various operations
     assert!(hospital.mark_patient_as_infected(80001).is_ok());
@@ -179,3 +179,86 @@ fn test_all_rules_together() {
     assert!(available.contains(&401)); // VIP discharged
     assert!(available.contains(&402)); // No longer blocked
 }
+
+#[test]
+fn test_switch_infectious_into_occupied_room_is_rejected() {
+    let mut hospital = Hospital::new();
+
+    let infected = Patient::new(90001, "Infected".to_string(), 40, Gender::Male, true, false);
+    let p2 = Patient::new(90002, "P2".to_string(), 35, Gender::Male, false, false);
+    let p3 = Patient::new(90003, "P3".to_string(), 45, Gender::Male, false, false);
+
+    hospital.admit_patient(&infected, 101).unwrap(); // Blocks 102
+    hospital.admit_patient(&p2, 201).unwrap();
+    hospital.admit_patient(&p3, 202).unwrap(); // Roommate of 201
+
+    // Infected patient would land next to P3 with no free bed to block
+    assert!(hospital.switch_patients(90001, 90002).is_err());
+
+    // Nothing changed
+    assert_eq!(hospital.find_patient_info(90001).unwrap().0, 101);
+    assert_eq!(hospital.find_patient_info(90002).unwrap().0, 201);
+    assert_eq!(hospital.count_beds_by_state(), (3, 148, 1, 0));
+}
+
+#[test]
+fn test_switch_infectious_into_empty_room_moves_block() {
+    let mut hospital = Hospital::new();
+
+    let infected = Patient::new(
+        90011,
+        "Infected".to_string(),
+        40,
+        Gender::Female,
+        true,
+        false,
+    );
+    let normal = Patient::new(
+        90012,
+        "Normal".to_string(),
+        35,
+        Gender::Female,
+        false,
+        false,
+    );
+
+    hospital.admit_patient(&infected, 101).unwrap(); // Blocks 102
+    hospital.admit_patient(&normal, 203).unwrap();
+
+    assert!(hospital.switch_patients(90011, 90012).is_ok());
+
+    let test = Patient::new(99999, "Test".to_string(), 30, Gender::Female, false, false);
+    let available = hospital.get_available_beds_for_patient(&test);
+    assert!(available.contains(&102)); // Old block released
+    assert!(!available.contains(&204)); // New block next to the infected patient
+}
+
+#[test]
+fn test_failed_admission_keeps_patient_for_retry() {
+    let mut hospital = Hospital::new();
+
+    let child = Patient::new(91001, "Child".to_string(), 8, Gender::Female, false, false);
+
+    // Wrong unit: the caller still owns the patient and can retry elsewhere
+    assert!(hospital.admit_patient(&child, 101).is_err());
+    assert!(hospital.admit_patient(&child, 501).is_ok());
+    assert_eq!(hospital.find_patient_info(91001), Some((501, child)));
+}
+
+#[test]
+fn test_bed_state_is_readable_through_accessors() {
+    use bed_management::domain::bed::BedState;
+    use bed_management::domain::constants::TOTAL_BEDS;
+
+    let mut hospital = Hospital::new();
+    let vip = Patient::new(92001, "VIP".to_string(), 50, Gender::Male, false, true);
+    hospital.admit_patient(&vip, 101).unwrap();
+
+    assert_eq!(hospital.bed_count(), TOTAL_BEDS);
+    assert_eq!(hospital.bed(101).unwrap().state, BedState::Occupied(vip));
+    assert!(hospital.bed(102).unwrap().is_blocked());
+    assert!(hospital.bed(301).is_none());
+
+    let numbers: Vec<u32> = hospital.iter_beds().map(|(n, _)| n).take(3).collect();
+    assert_eq!(numbers, vec![101, 102, 103]);
+}